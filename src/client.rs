@@ -1,22 +1,66 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Instant, SystemTime};
 /// A client for the URL shortener API.
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+#[cfg(target_arch = "wasm32")]
+use web_time::{web::SystemTimeExt, Instant, SystemTime};
+
+use reqwest::{
+    header::{
+        HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, DATE,
+        PROXY_AUTHORIZATION, RETRY_AFTER,
+    },
+    StatusCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+// Resolving and following short URLs, which most of these are for, isn't available on wasm32.
+#[cfg_attr(target_arch = "wasm32", allow(unused_imports))]
 use crate::{
     errors::{ApiError, UrlShortenerError, ValidationError},
     requests::{
-        EmojiRequest, EmojiResponse, ExportRequest, ExportResponse, ShortenRequest,
-        ShortenResponse, StatsRequest, StatsResponse,
+        is_on_host, EmojiRequest, EmojiResponse, ExportFormat, ExportRequest, ExportResponse,
+        FollowResult, ResolveRequest, ResolveResponse, ShortenRequest, ShortenResponse,
+        StatsRequest, StatsResponse,
     },
-    utils::{is_valid_alias, is_valid_max_clicks, is_valid_password, is_valid_url},
+    retry::RetryPolicy,
+    utils::{is_valid_short_code, percent_encode_url},
+};
+
+#[cfg(not(feature = "blocking"))]
+use futures::{
+    future,
+    stream::{self, StreamExt},
 };
+#[cfg(feature = "blocking")]
+use std::thread;
+
+#[cfg(feature = "custom_url")]
+use crate::requests::DeleteRequest;
+#[cfg(feature = "qr")]
+use crate::requests::{QrOptions, QrResponse};
+#[cfg(feature = "url")]
+use crate::utils::{normalize_url, NormalizeOptions};
+#[cfg(all(feature = "cancellation", not(feature = "blocking")))]
+use tokio_util::sync::CancellationToken;
 
 /// A client for the URL shortener API.
 ///
 /// This client can be used in both async and blocking modes, depending on the feature flags.
+/// It is `Send` and `Sync`, so it can be shared between threads or tasks, such as the handlers
+/// of a web server, behind an `Arc`.
+///
+/// Cloning the client is cheap, as clones share its configuration and connection pool until one
+/// of them is reconfigured.
 ///
 /// # Example usage:
 /// ```rust
-/// use spoo_me::client::UrlShortenerClient;
-/// use spoo_me::requests::ShortenRequest;
-/// use spoo_me::errors::UrlShortenerError;
+/// use spoo_me::prelude::*;
 ///
 /// #[cfg(not(feature = "blocking"))]
 /// #[tokio::main]
@@ -28,7 +72,7 @@ use crate::{
 ///         .block_bots(true);
 ///
 ///     let response = client.shorten(request).await?;
-///     println!("Shortened URL: {}", response.short_url);
+///     println!("Shortened URL: {}", response);
 ///     Ok(())
 /// }
 ///
@@ -41,574 +85,2573 @@ use crate::{
 ///         .block_bots(true);
 ///
 ///     let response = client.shorten_blocking(request)?;
-///     println!("Shortened URL: {}", response.short_url);
+///     println!("Shortened URL: {}", response);
 ///     Ok(())
 /// }
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct UrlShortenerClient {
+    inner: Arc<ClientInner>,
+}
+
+/// The configuration of a [`UrlShortenerClient`], shared by its clones until one of them is
+/// reconfigured.
+#[derive(Clone)]
+struct ClientInner {
     base_url: String,
-    #[cfg(not(feature = "blocking"))]
-    client: reqwest::Client,
-    #[cfg(feature = "blocking")]
-    client: reqwest::blocking::Client,
+    client: HttpClient,
+    timeout: Option<Duration>,
+    user_agent: String,
+    retry_policy: RetryPolicy,
+    api_retry: ApiRetryPredicate,
+    on_rate_limit: Option<RateLimitCallback>,
+    header_provider: Option<HeaderProvider>,
+    sensitive_headers: Vec<HeaderName>,
+    default_headers: HeaderMap,
+    stats_cache: Option<Arc<StatsCache>>,
+    max_clicks_limit: Option<u32>,
+    default_max_clicks: Option<u32>,
+    default_block_bots: Option<bool>,
+    connection: ConnectionOptions,
+    #[cfg(feature = "url")]
+    url_normalization: Option<NormalizeOptions>,
 }
 
-impl UrlShortenerClient {
-    /// Create a new client
-    pub fn new() -> Self {
-        UrlShortenerClient {
-            base_url: "https://spoo.me".to_string(),
-            #[cfg(not(feature = "blocking"))]
-            client: reqwest::Client::new(),
-            #[cfg(feature = "blocking")]
-            client: reqwest::blocking::Client::new(),
+/// Stats responses kept for a while, shared by all clones of a client.
+#[derive(Debug)]
+struct StatsCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, StatsResponse)>>,
+}
+
+impl StatsCache {
+    fn new(ttl: Duration) -> Self {
+        StatsCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Create a new client with a custom base URL
-    ///
-    /// Requires the `custom_url` feature to be enabled.
-    #[cfg(feature = "custom_url")]
-    pub fn new_with_base_url<S: Into<String>>(url: S) -> Self {
-        UrlShortenerClient {
-            base_url: url.into(),
-            #[cfg(not(feature = "blocking"))]
-            client: reqwest::Client::new(),
-            #[cfg(feature = "blocking")]
-            client: reqwest::blocking::Client::new(),
-        }
+    /// The cached stats of `short_code`, if they are younger than the TTL.
+    fn get(&self, short_code: &str) -> Option<StatsResponse> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(short_code)
+            .filter(|(cached_at, _)| cached_at.elapsed() < self.ttl)
+            .map(|(_, stats)| stats.clone())
     }
 
-    /// Set a custom base URL for the client.
-    ///
-    /// Requires the `custom_url` feature to be enabled.
-    #[cfg(feature = "custom_url")]
-    pub fn set_base_url<T: Into<String>>(&mut self, url: T) {
-        self.base_url = url.into();
+    fn insert(&self, short_code: &str, stats: &StatsResponse) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (cached_at, _)| cached_at.elapsed() < self.ttl);
+        entries.insert(short_code.to_string(), (Instant::now(), stats.clone()));
     }
 
-    /// Shorten a URL (async mode).
-    #[cfg(not(feature = "blocking"))]
-    pub async fn shorten(&self, req: ShortenRequest) -> Result<ShortenResponse, UrlShortenerError> {
-        if let Some(ref pw) = req.password {
-            if !is_valid_password(pw) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidPasswordFormat(pw.clone()),
-                ));
-            }
-        }
+    fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
 
-        #[cfg(feature = "custom_url")]
-        if !is_valid_url(&req.url, &self.base_url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
-        #[cfg(not(feature = "custom_url"))]
-        if !is_valid_url(&req.url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
+/// The reqwest client, depending on whether the `blocking` feature is enabled.
+#[cfg(not(feature = "blocking"))]
+type ReqwestClient = reqwest::Client;
+#[cfg(feature = "blocking")]
+type ReqwestClient = reqwest::blocking::Client;
+
+/// The underlying HTTP client, which wraps [`ReqwestClient`] in a middleware stack when the
+/// `middleware` feature is enabled in async mode.
+#[cfg(not(all(feature = "middleware", not(feature = "blocking"))))]
+type HttpClient = ReqwestClient;
+#[cfg(all(feature = "middleware", not(feature = "blocking")))]
+type HttpClient = reqwest_middleware::ClientWithMiddleware;
+
+/// The request builder of [`HttpClient`].
+#[cfg(all(not(feature = "blocking"), not(feature = "middleware")))]
+type HttpRequestBuilder = reqwest::RequestBuilder;
+#[cfg(all(not(feature = "blocking"), feature = "middleware"))]
+type HttpRequestBuilder = reqwest_middleware::RequestBuilder;
+#[cfg(feature = "blocking")]
+type HttpRequestBuilder = reqwest::blocking::RequestBuilder;
+
+/// The builder of [`ReqwestClient`].
+#[cfg(not(feature = "blocking"))]
+type ReqwestClientBuilder = reqwest::ClientBuilder;
+#[cfg(feature = "blocking")]
+type ReqwestClientBuilder = reqwest::blocking::ClientBuilder;
+
+/// Connection pool, HTTP/2 and decompression settings of the underlying HTTP client. Unset
+/// options keep reqwest's defaults.
+///
+/// None of them can be set on the fetch-based client of wasm32 targets, so they are ignored there.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+struct ConnectionOptions {
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    http2_prior_knowledge: bool,
+    no_decompression: bool,
+}
 
-        if let Some(ref alias) = req.alias {
-            if !is_valid_alias(alias) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidAliasFormat(alias.clone()),
-                ));
-            }
+impl ConnectionOptions {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn apply(&self, mut builder: ReqwestClientBuilder) -> ReqwestClientBuilder {
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
         }
-
-        if let Some(max_clicks) = req.max_clicks {
-            if !is_valid_max_clicks(max_clicks) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidMaxClicks(max_clicks),
-                ));
-            }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
         }
-
-        let resp = self
-            .client
-            .post(format!("{}/", self.base_url))
-            .header("Accept", "application/json")
-            .form(&req)
-            .send()
-            .await
-            .map_err(UrlShortenerError::Http)?;
-
-        let status = resp.status();
-        let text = resp.text().await.map_err(UrlShortenerError::Http)?;
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if self.no_decompression {
+            #[cfg(feature = "gzip")]
+            {
+                builder = builder.no_gzip();
             }
-
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        _ => ApiError::UrlError,
-                    }));
-                }
+            #[cfg(feature = "brotli")]
+            {
+                builder = builder.no_brotli();
             }
-            return Err(UrlShortenerError::Other(text));
         }
+        builder
+    }
 
-        let result =
-            serde_json::from_str::<ShortenResponse>(&text).map_err(UrlShortenerError::Json)?;
-
-        Ok(result)
+    #[cfg(target_arch = "wasm32")]
+    fn apply(&self, builder: ReqwestClientBuilder) -> ReqwestClientBuilder {
+        builder
     }
+}
 
-    /// Shorten a URL (blocking mode).
-    #[cfg(feature = "blocking")]
-    pub fn shorten_blocking(
-        &self,
-        req: ShortenRequest,
-    ) -> Result<ShortenResponse, UrlShortenerError> {
-        if let Some(ref pw) = req.password {
-            if !is_valid_password(pw) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidPasswordFormat(pw.clone()),
-                ));
-            }
-        }
+/// Build the underlying HTTP client.
+fn build_http_client(
+    timeout: Option<Duration>,
+    user_agent: &str,
+    connection: &ConnectionOptions,
+) -> HttpClient {
+    let builder = connection.apply(ReqwestClient::builder().user_agent(user_agent));
+    // The fetch-based client of wasm32 targets only supports timeouts per request.
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    };
+    #[cfg(target_arch = "wasm32")]
+    let _ = timeout;
+    http_client(builder.build().expect("failed to build the HTTP client"))
+}
 
-        #[cfg(feature = "custom_url")]
-        if !is_valid_url(&req.url, &self.base_url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
-        #[cfg(not(feature = "custom_url"))]
-        if !is_valid_url(&req.url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
+/// Wrap a reqwest client as the underlying HTTP client, with no middleware.
+#[cfg(not(all(feature = "middleware", not(feature = "blocking"))))]
+fn http_client(client: ReqwestClient) -> HttpClient {
+    client
+}
 
-        if let Some(ref alias) = req.alias {
-            if !is_valid_alias(alias) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidAliasFormat(alias.clone()),
-                ));
-            }
-        }
+/// Wrap a reqwest client as the underlying HTTP client, with no middleware.
+#[cfg(all(feature = "middleware", not(feature = "blocking")))]
+fn http_client(client: ReqwestClient) -> HttpClient {
+    HttpClient::from(client)
+}
 
-        if let Some(max_clicks) = req.max_clicks {
-            if !is_valid_max_clicks(max_clicks) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidMaxClicks(max_clicks),
-                ));
-            }
-        }
+/// How many characters of an unexpected error body are kept in [`UrlShortenerError::Unexpected`].
+pub const ERROR_BODY_SNIPPET_LEN: usize = 200;
 
-        let resp = self
-            .client
-            .post(format!("{}/", self.base_url))
-            .header("Accept", "application/json")
-            .form(&req)
-            .send()
-            .map_err(UrlShortenerError::Http)?;
-
-        let status = resp.status();
-        let text = resp.text().map_err(UrlShortenerError::Http)?;
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
-            }
+/// How long `shorten_then_stats` waits before asking for the stats of a new short URL again.
+pub const STATS_RETRY_DELAY: Duration = Duration::from_secs(1);
 
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        _ => ApiError::UrlError,
-                    }));
-                }
-            }
-            return Err(UrlShortenerError::Other(text));
-        }
+/// The most redirects `follow` goes through before giving up.
+pub const MAX_REDIRECTS: usize = 10;
 
-        let result =
-            serde_json::from_str::<ShortenResponse>(&text).map_err(UrlShortenerError::Json)?;
+/// The `User-Agent` sent by clients that don't set their own.
+pub const DEFAULT_USER_AGENT: &str = concat!("spoo-me-rust-sdk/", env!("CARGO_PKG_VERSION"));
 
-        Ok(result)
+/// Strip a trailing slash from a base URL, so routes can be joined to it with a single `/`.
+fn normalize_base_url(url: String) -> String {
+    match url.strip_suffix('/') {
+        Some(url) => url.to_string(),
+        None => url,
     }
+}
 
-    /// Create an emoji URL (async mode).
-    #[cfg(not(feature = "blocking"))]
-    pub async fn emoji(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
-        if let Some(ref pw) = req.password {
-            if !is_valid_password(pw) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidPasswordFormat(pw.clone()),
-                ));
-            }
-        }
+/// Decides whether a request that failed with the given [`ApiError`] should be retried.
+type ApiRetryPredicate = Arc<dyn Fn(&ApiError) -> bool + Send + Sync>;
 
-        #[cfg(feature = "custom_url")]
-        if !is_valid_url(&req.url, &self.base_url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
-        #[cfg(not(feature = "custom_url"))]
-        if !is_valid_url(&req.url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
+/// Called with the `Retry-After` delay, if any, whenever the API rate limits a request.
+type RateLimitCallback = Arc<dyn Fn(Option<Duration>) + Send + Sync>;
 
-        if let Some(max_clicks) = req.max_clicks {
-            if !is_valid_max_clicks(max_clicks) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidMaxClicks(max_clicks),
-                ));
-            }
-        }
+/// Called for every request to get extra headers to send with it.
+type HeaderProvider = Arc<dyn Fn() -> HeaderMap + Send + Sync>;
 
-        let resp = self
-            .client
-            .post(format!("{}/emoji", self.base_url))
-            .header("Accept", "application/json")
-            .form(&req)
-            .send()
-            .await
-            .map_err(UrlShortenerError::Http)?;
+fn default_api_retry() -> ApiRetryPredicate {
+    Arc::new(|err| matches!(err, ApiError::RateLimitExceeded { .. }))
+}
 
-        let status = resp.status();
-        let text = resp.text().await.map_err(UrlShortenerError::Http)?;
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
-            }
+/// Headers whose values are redacted by default.
+fn default_sensitive_headers() -> Vec<HeaderName> {
+    vec![
+        AUTHORIZATION,
+        PROXY_AUTHORIZATION,
+        HeaderName::from_static("x-api-key"),
+    ]
+}
 
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        err => ApiError::Other(err.to_string()),
-                    }));
-                }
-            }
-            return Err(UrlShortenerError::Other(text));
-        }
+/// A builder for [`UrlShortenerClient`].
+///
+/// The underlying HTTP client is built once, in [`build`](Self::build), so all HTTP options are
+/// applied together.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use spoo_me::{client::UrlShortenerClient, retry::RetryPolicy};
+///
+/// let client = UrlShortenerClient::builder()
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .retry_policy(RetryPolicy::new(3))
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct UrlShortenerClientBuilder {
+    base_url: String,
+    timeout: Option<Duration>,
+    user_agent: String,
+    retry_policy: RetryPolicy,
+    default_headers: HeaderMap,
+    stats_cache_ttl: Option<Duration>,
+    max_clicks_limit: Option<u32>,
+    default_max_clicks: Option<u32>,
+    default_block_bots: Option<bool>,
+    connection: ConnectionOptions,
+}
 
-        let result =
-            serde_json::from_str::<EmojiResponse>(&text).map_err(UrlShortenerError::Json)?;
+impl Default for UrlShortenerClientBuilder {
+    fn default() -> Self {
+        UrlShortenerClientBuilder {
+            base_url: "https://spoo.me".to_string(),
+            timeout: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            retry_policy: RetryPolicy::none(),
+            default_headers: HeaderMap::new(),
+            stats_cache_ttl: None,
+            max_clicks_limit: None,
+            default_max_clicks: None,
+            default_block_bots: None,
+            connection: ConnectionOptions::default(),
+        }
+    }
+}
 
-        Ok(result)
+impl UrlShortenerClientBuilder {
+    /// Set a custom base URL for the client.
+    ///
+    /// Requires the `custom_url` feature to be enabled.
+    #[cfg(feature = "custom_url")]
+    pub fn base_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.base_url = url.into();
+        self
     }
 
-    /// Create an emoji URL (blocking mode).
-    #[cfg(feature = "blocking")]
-    pub fn emoji_blocking(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
-        if let Some(ref pw) = req.password {
-            if !is_valid_password(pw) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidPasswordFormat(pw.clone()),
-                ));
-            }
-        }
+    /// Set the timeout for requests made by the client.
+    ///
+    /// See [`UrlShortenerClient::set_timeout`] for what the timeout covers.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 
-        #[cfg(feature = "custom_url")]
-        if !is_valid_url(&req.url, &self.base_url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
-        #[cfg(not(feature = "custom_url"))]
-        if !is_valid_url(&req.url) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidUrlFormat(req.url.clone()),
-            ));
-        }
+    /// Set the `User-Agent` header sent with every request.
+    ///
+    /// Defaults to [`DEFAULT_USER_AGENT`].
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
 
-        if let Some(max_clicks) = req.max_clicks {
-            if !is_valid_max_clicks(max_clicks) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidMaxClicks(max_clicks),
-                ));
-            }
-        }
+    /// Set the policy for retrying requests that fail with a retryable [`ApiError`].
+    ///
+    /// See [`UrlShortenerClient::with_retry_policy`].
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
 
-        let resp = self
-            .client
-            .post(format!("{}/emoji", self.base_url))
-            .header("Accept", "application/json")
-            .form(&req)
-            .send()
-            .map_err(UrlShortenerError::Http)?;
-
-        let status = resp.status();
-        let text = resp.text().map_err(UrlShortenerError::Http)?;
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
-            }
+    /// Set headers sent with every request, such as an API key for a self-hosted instance.
+    ///
+    /// They replace the SDK's own headers of the same name, so an `Accept` header here overrides
+    /// the default `application/json`. `Content-Type` is ignored, as it always matches the body.
+    pub fn default_headers(mut self, mut headers: HeaderMap) -> Self {
+        headers.remove(CONTENT_TYPE);
+        self.default_headers = headers;
+        self
+    }
 
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        _ => ApiError::UrlError,
-                    }));
-                }
-            }
-            return Err(UrlShortenerError::Other(text));
-        }
+    /// Cache stats responses for `ttl`.
+    ///
+    /// See [`UrlShortenerClient::with_stats_cache`].
+    pub fn stats_cache(mut self, ttl: Duration) -> Self {
+        self.stats_cache_ttl = Some(ttl);
+        self
+    }
 
-        let result =
-            serde_json::from_str::<EmojiResponse>(&text).map_err(UrlShortenerError::Json)?;
+    /// Reject max-clicks values above `limit` before sending a request.
+    ///
+    /// See [`UrlShortenerClient::with_max_clicks_limit`].
+    pub fn max_clicks_limit(mut self, limit: u32) -> Self {
+        self.max_clicks_limit = Some(limit);
+        self
+    }
 
-        Ok(result)
+    /// Set the max-clicks limit of shorten and emoji requests that don't set their own.
+    pub fn default_max_clicks(mut self, max_clicks: u32) -> Self {
+        self.default_max_clicks = Some(max_clicks);
+        self
     }
 
-    /// Get statistics for a shortened URL (async mode).
-    #[cfg(not(feature = "blocking"))]
-    pub async fn stats(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
-        if req.short_code.is_empty() {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidPasswordFormat("Short code cannot be empty".to_string()),
-            ));
-        }
+    /// Set whether bots are blocked for shorten and emoji requests that don't say so themselves.
+    pub fn default_block_bots(mut self, block_bots: bool) -> Self {
+        self.default_block_bots = Some(block_bots);
+        self
+    }
 
-        if let Some(ref pw) = req.password {
-            if !is_valid_password(pw) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidPasswordFormat(pw.clone()),
-                ));
-            }
-        }
+    /// Keep at most `max` idle connections per host in the connection pool.
+    ///
+    /// Higher values keep more connections ready for bursts of concurrent requests, at the cost
+    /// of more open sockets; `0` disables reusing connections. Defaults to reqwest's default of no limit.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.connection.pool_max_idle_per_host = Some(max);
+        self
+    }
 
-        if !is_valid_alias(&req.short_code) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidAliasFormat(req.short_code.clone()),
-            ));
-        }
+    /// Close pooled connections once they have been idle for `timeout`, or never if `None`.
+    ///
+    /// Longer timeouts save reconnecting, including the TLS handshake, between batches of
+    /// requests, but the server or a proxy may still close idle connections on its own. Defaults
+    /// to reqwest's default of 90 seconds.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connection.pool_idle_timeout = Some(timeout);
+        self
+    }
 
-        let resp = self
-            .client
-            .post(format!("{}/stats/{}", self.base_url, req.short_code))
-            .header("Accept", "application/json")
-            .form(&req)
-            .send()
-            .await
-            .map_err(UrlShortenerError::Http)?;
+    /// Speak HTTP/2 straight away, without negotiating it first.
+    ///
+    /// HTTP/2 sends concurrent requests over a single connection, which cuts connection churn
+    /// for batch workloads. It is already used when negotiated over TLS, so this is mainly for
+    /// plain HTTP instances known to support HTTP/2; requests to a server that doesn't will fail.
+    /// Off by default.
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.connection.http2_prior_knowledge = true;
+        self
+    }
 
-        let status = resp.status();
-        let text = resp.text().await.map_err(UrlShortenerError::Http)?;
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
-            }
+    /// Ask for compressed responses and decompress them, for the encodings enabled by the `gzip`
+    /// and `brotli` features.
+    ///
+    /// On by default when either feature is enabled, which mostly helps large exports. Responses,
+    /// including [`ExportResponse::data`], are always handed over decompressed. Has no effect
+    /// without either feature.
+    pub fn decompression(mut self, enabled: bool) -> Self {
+        self.connection.no_decompression = !enabled;
+        self
+    }
 
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        _ => ApiError::UrlError,
-                    }));
-                }
-            }
-            return Err(UrlShortenerError::Other(text));
+    /// Build the client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client cannot be built, for example when the user agent is
+    /// not a valid header value or the TLS backend fails to initialize.
+    pub fn build(self) -> UrlShortenerClient {
+        UrlShortenerClient {
+            inner: Arc::new(ClientInner {
+                client: build_http_client(self.timeout, &self.user_agent, &self.connection),
+                base_url: normalize_base_url(self.base_url),
+                timeout: self.timeout,
+                user_agent: self.user_agent,
+                retry_policy: self.retry_policy,
+                api_retry: default_api_retry(),
+                on_rate_limit: None,
+                header_provider: None,
+                sensitive_headers: default_sensitive_headers(),
+                default_headers: self.default_headers,
+                stats_cache: self
+                    .stats_cache_ttl
+                    .map(|ttl| Arc::new(StatsCache::new(ttl))),
+                max_clicks_limit: self.max_clicks_limit,
+                default_max_clicks: self.default_max_clicks,
+                default_block_bots: self.default_block_bots,
+                connection: self.connection,
+                #[cfg(feature = "url")]
+                url_normalization: None,
+            }),
         }
+    }
+}
 
-        let result =
-            serde_json::from_str::<StatsResponse>(&text).map_err(UrlShortenerError::Json)?;
+impl fmt::Debug for UrlShortenerClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UrlShortenerClient")
+            .field("base_url", &self.inner.base_url)
+            .field("client", &self.inner.client)
+            .field("timeout", &self.inner.timeout)
+            .field("user_agent", &self.inner.user_agent)
+            .field("retry_policy", &self.inner.retry_policy)
+            .field("sensitive_headers", &self.inner.sensitive_headers)
+            .field("max_clicks_limit", &self.inner.max_clicks_limit)
+            .field("default_max_clicks", &self.inner.default_max_clicks)
+            .field("default_block_bots", &self.inner.default_block_bots)
+            .field("connection", &self.inner.connection)
+            .field(
+                "stats_cache_ttl",
+                &self.inner.stats_cache.as_ref().map(|cache| cache.ttl),
+            )
+            .field(
+                "default_headers",
+                &self.redact_headers(&self.inner.default_headers),
+            )
+            .finish_non_exhaustive()
+    }
+}
 
-        Ok(result)
+impl UrlShortenerClient {
+    /// The configuration of this client, copied first if other clones share it.
+    fn inner_mut(&mut self) -> &mut ClientInner {
+        Arc::make_mut(&mut self.inner)
     }
 
-    /// Get statistics for a shortened URL (blocking mode).
-    #[cfg(feature = "blocking")]
-    pub fn stats_blocking(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
-        if req.short_code.is_empty() {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidPasswordFormat("Short code cannot be empty".to_string()),
-            ));
-        }
+    /// Create a new client
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
 
-        if let Some(ref pw) = req.password {
-            if !is_valid_password(pw) {
-                return Err(UrlShortenerError::Validation(
-                    ValidationError::InvalidPasswordFormat(pw.clone()),
-                ));
-            }
-        }
+    /// Start configuring a new client with a [`UrlShortenerClientBuilder`].
+    pub fn builder() -> UrlShortenerClientBuilder {
+        UrlShortenerClientBuilder::default()
+    }
 
-        if !is_valid_alias(&req.short_code) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidAliasFormat(req.short_code.clone()),
-            ));
-        }
+    /// Create a new client whose requests time out after `timeout`.
+    ///
+    /// See [`set_timeout`](Self::set_timeout) for what the timeout covers.
+    pub fn new_with_timeout(timeout: Duration) -> Self {
+        Self::builder().timeout(timeout).build()
+    }
 
-        let resp = self
-            .client
-            .post(format!("{}/stats/{}", self.base_url, req.short_code))
-            .header("Accept", "application/json")
-            .form(&req)
-            .send()
-            .map_err(UrlShortenerError::Http)?;
-
-        let status = resp.status();
-        let text = resp.text().map_err(UrlShortenerError::Http)?;
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
-            }
+    /// Create a new client that sends its requests through an existing [`reqwest::Client`].
+    ///
+    /// This lets the client share connection pooling, proxy and TLS settings with the rest of an
+    /// application. The default base URL is used, and the injected client's own `User-Agent`
+    /// is kept.
+    #[cfg(not(feature = "blocking"))]
+    pub fn with_client(client: reqwest::Client) -> Self {
+        let mut this = Self::new();
+        this.inner_mut().client = http_client(client);
+        this
+    }
 
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        _ => ApiError::UrlError,
-                    }));
-                }
-            }
-            return Err(UrlShortenerError::Other(text));
-        }
+    /// Create a new client that sends its requests through a
+    /// [`reqwest_middleware::ClientWithMiddleware`].
+    ///
+    /// Every endpoint goes through the middleware stack, except [`resolve`](Self::resolve),
+    /// [`follow`](Self::follow) and [`verify_redirect`](Self::verify_redirect), which need a
+    /// client that doesn't follow redirects. As with [`with_client`](Self::with_client), the
+    /// default base URL is used and the injected client's own `User-Agent` is kept.
+    ///
+    /// Requires the `middleware` feature to be enabled.
+    ///
+    /// # Example
+    ///
+    /// Retrying transient failures with the `reqwest-retry` crate:
+    ///
+    /// ```rust,ignore
+    /// use reqwest_middleware::ClientBuilder;
+    /// use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+    /// use spoo_me::client::UrlShortenerClient;
+    ///
+    /// let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+    /// let client = ClientBuilder::new(reqwest::Client::new())
+    ///     .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+    ///     .build();
+    /// let client = UrlShortenerClient::with_middleware_client(client);
+    /// ```
+    #[cfg(all(feature = "middleware", not(feature = "blocking")))]
+    pub fn with_middleware_client(client: reqwest_middleware::ClientWithMiddleware) -> Self {
+        let mut this = Self::new();
+        this.inner_mut().client = client;
+        this
+    }
 
-        let result =
-            serde_json::from_str::<StatsResponse>(&text).map_err(UrlShortenerError::Json)?;
+    /// Create a new client that sends its requests through an existing
+    /// [`reqwest::blocking::Client`].
+    ///
+    /// This lets the client share connection pooling, proxy and TLS settings with the rest of an
+    /// application. The default base URL is used, and the injected client's own `User-Agent`
+    /// is kept.
+    #[cfg(feature = "blocking")]
+    pub fn with_blocking_client(client: reqwest::blocking::Client) -> Self {
+        let mut this = Self::new();
+        this.inner_mut().client = client;
+        this
+    }
 
-        Ok(result)
+    /// Create a new client with a custom base URL
+    ///
+    /// The base URL may include a path, such as `https://tools.example.com/spoo` for an instance
+    /// behind a reverse proxy. A trailing slash is ignored.
+    ///
+    /// Requires the `custom_url` feature to be enabled.
+    #[cfg(feature = "custom_url")]
+    pub fn new_with_base_url<S: Into<String>>(url: S) -> Self {
+        let mut this = Self::new();
+        this.inner_mut().base_url = normalize_base_url(url.into());
+        this
     }
 
-    /// Export data for a shortened URL (async mode).
-    #[cfg(not(feature = "blocking"))]
-    pub async fn export(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError> {
-        if req.short_code.is_empty() {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidAliasFormat(req.short_code),
-            ));
-        }
+    /// Set a custom base URL for the client.
+    ///
+    /// Requires the `custom_url` feature to be enabled.
+    #[cfg(feature = "custom_url")]
+    pub fn set_base_url<T: Into<String>>(&mut self, url: T) {
+        self.inner_mut().base_url = normalize_base_url(url.into());
+    }
 
-        if !is_valid_alias(&req.short_code) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidAliasFormat(req.short_code.clone()),
-            ));
-        }
+    /// The base URL requests are sent to, without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        &self.inner.base_url
+    }
 
-        let resp = self
-            .client
-            .post(format!(
-                "{}/export/{}/{}",
-                self.base_url, req.short_code, req.export_format
-            ))
-            .form(&req)
-            .send()
-            .await
-            .map_err(UrlShortenerError::Http)?;
+    /// Set the timeout for requests made by the client.
+    ///
+    /// The timeout covers the whole request, from connecting until the response body has been
+    /// read. Requests that exceed it fail with [`UrlShortenerError::Http`]. By default async
+    /// clients have no timeout, while blocking clients use reqwest's default of 30 seconds.
+    ///
+    /// This rebuilds the underlying HTTP client, discarding any injected client and its settings.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        let inner = self.inner_mut();
+        inner.timeout = Some(timeout);
+        inner.client = build_http_client(inner.timeout, &inner.user_agent, &inner.connection);
+    }
 
-        let status = resp.status();
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
-            }
+    /// Set the `User-Agent` header sent with every request.
+    ///
+    /// Defaults to [`DEFAULT_USER_AGENT`]. Some WAFs in front of self-hosted instances block
+    /// generic user agents, so this can be used to identify an application instead.
+    ///
+    /// This rebuilds the underlying HTTP client, discarding any injected client and its settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `user_agent` is not a valid header value.
+    pub fn set_user_agent<S: Into<String>>(&mut self, user_agent: S) {
+        let inner = self.inner_mut();
+        inner.user_agent = user_agent.into();
+        inner.client = build_http_client(inner.timeout, &inner.user_agent, &inner.connection);
+    }
 
-            let text = resp.text().await.map_err(UrlShortenerError::Http)?;
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        _ => ApiError::Other(err.to_string()),
-                    }));
-                }
-            }
-            return Err(UrlShortenerError::Other(text));
-        }
+    /// Set the policy for retrying requests that fail with a retryable [`ApiError`].
+    ///
+    /// Defaults to [`RetryPolicy::none`], meaning requests are never retried. Once retries run
+    /// out, the last error (such as [`ApiError::RateLimitExceeded`]) is returned.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.inner_mut().retry_policy = policy;
+        self
+    }
 
-        let data = resp.bytes().await.map_err(UrlShortenerError::Http)?;
-        let result = ExportResponse {
-            data: data.to_vec(),
-        };
+    /// Cache stats responses for `ttl`, so repeated stats requests for a short code within it are
+    /// answered without contacting the server.
+    ///
+    /// Responses are cached by short code and shared by all clones of the client. Requests
+    /// carrying a password always go to the server and are never cached, so stats of a
+    /// password-protected link can't be read from the cache without the password. By default
+    /// nothing is cached.
+    pub fn with_stats_cache(mut self, ttl: Duration) -> Self {
+        self.inner_mut().stats_cache = Some(Arc::new(StatsCache::new(ttl)));
+        self
+    }
 
-        Ok(result)
+    /// Reject max-clicks values above `limit` with [`ValidationError::MaxClicksAboveLimit`],
+    /// before sending a request.
+    ///
+    /// There's no limit by default, as the spoo.me API documents no maximum and self-hosted
+    /// instances may set their own, so any positive value is sent and the server decides. Set
+    /// this to the maximum your instance accepts to catch values that would fail with
+    /// [`ApiError::MaxClicksError`].
+    pub fn with_max_clicks_limit(mut self, limit: u32) -> Self {
+        self.inner_mut().max_clicks_limit = Some(limit);
+        self
     }
 
-    /// Export data for a shortened URL (blocking mode).
-    #[cfg(feature = "blocking")]
-    pub fn export_blocking(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError> {
-        if req.short_code.is_empty() {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidAliasFormat(req.short_code),
-            ));
+    /// Forget all cached stats responses.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.inner.stats_cache {
+            cache.clear();
         }
+    }
 
-        if !is_valid_alias(&req.short_code) {
-            return Err(UrlShortenerError::Validation(
-                ValidationError::InvalidAliasFormat(req.short_code.clone()),
-            ));
-        }
+    /// Set which [`ApiError`]s are considered transient and worth retrying.
+    ///
+    /// By default only [`ApiError::RateLimitExceeded`] is retried. This has no effect unless
+    /// retries are enabled with [`with_retry_policy`](Self::with_retry_policy).
+    ///
+    /// ```rust
+    /// use spoo_me::{client::UrlShortenerClient, errors::ApiError, retry::RetryPolicy};
+    ///
+    /// let client = UrlShortenerClient::new()
+    ///     .with_retry_policy(RetryPolicy::new(3))
+    ///     .with_api_retry(|err| match err {
+    ///         ApiError::RateLimitExceeded { .. } => true,
+    ///         ApiError::Other(msg) => msg.contains("db busy"),
+    ///         _ => false,
+    ///     });
+    /// ```
+    pub fn with_api_retry<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&ApiError) -> bool + Send + Sync + 'static,
+    {
+        self.inner_mut().api_retry = Arc::new(predicate);
+        self
+    }
 
-        let resp = self
-            .client
-            .post(format!(
-                "{}/export/{}/{}",
-                self.base_url, req.short_code, req.export_format
-            ))
-            .form(&req)
-            .send()
-            .map_err(UrlShortenerError::Http)?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            if status.as_u16() == 429 {
-                return Err(UrlShortenerError::Api(ApiError::RateLimitExceeded));
-            }
+    /// Call `callback` whenever a request is rate limited with a `429` response.
+    ///
+    /// The callback gets the `Retry-After` delay, if the server sent one, and runs before any
+    /// retry, so every rate-limited attempt is reported. This makes it easy to count rate limits,
+    /// for example in a metric, without a logging dependency. Off by default.
+    ///
+    /// ```rust
+    /// use std::sync::{
+    ///     atomic::{AtomicU64, Ordering},
+    ///     Arc,
+    /// };
+    /// use spoo_me::client::UrlShortenerClient;
+    ///
+    /// let rate_limited = Arc::new(AtomicU64::new(0));
+    /// let counter = Arc::clone(&rate_limited);
+    /// let client = UrlShortenerClient::new().with_rate_limit_callback(move |_retry_after| {
+    ///     counter.fetch_add(1, Ordering::Relaxed);
+    /// });
+    /// ```
+    pub fn with_rate_limit_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Option<Duration>) + Send + Sync + 'static,
+    {
+        self.inner_mut().on_rate_limit = Some(Arc::new(callback));
+        self
+    }
 
-            let text = resp.text().map_err(UrlShortenerError::Http)?;
-            if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&text) {
-                if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
-                    return Err(UrlShortenerError::Api(match err {
-                        "UrlError" => ApiError::UrlError,
-                        "AliasError" => ApiError::AliasError,
-                        "PasswordError" => ApiError::PasswordError,
-                        "MaxClicksError" => ApiError::MaxClicksError,
-                        "EmojiError" => ApiError::EmojiError,
-                        _ => ApiError::Other(err.to_string()),
-                    }));
-                }
-            }
-            return Err(UrlShortenerError::Other(text));
+    /// Call `provider` for the headers of every request, such as an `X-Request-Id` or
+    /// `traceparent` header propagating the caller's context.
+    ///
+    /// Unlike [default headers](UrlShortenerClientBuilder::default_headers), the headers can
+    /// change from one request to the next. The provider is called again for each retry. Its
+    /// headers override default headers of the same name, but not headers set on the request
+    /// itself, and `Accept` and `Content-Type` headers from it are ignored, so the SDK still
+    /// parses every response. Off by default.
+    ///
+    /// ```rust
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    /// use spoo_me::client::UrlShortenerClient;
+    ///
+    /// let client = UrlShortenerClient::new().with_header_provider(|| {
+    ///     let mut headers = HeaderMap::new();
+    ///     let request_id = uuid::Uuid::new_v4().to_string();
+    ///     headers.insert("x-request-id", HeaderValue::from_str(&request_id).unwrap());
+    ///     headers
+    /// });
+    /// ```
+    pub fn with_header_provider<F>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> HeaderMap + Send + Sync + 'static,
+    {
+        self.inner_mut().header_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Run the rate limit callback, if any, if `result` is a rate limit error.
+    fn notify_rate_limit<T>(&self, result: &Result<T, UrlShortenerError>) {
+        if let (
+            Some(callback),
+            Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { retry_after })),
+        ) = (&self.inner.on_rate_limit, result)
+        {
+            callback(*retry_after);
+        }
+    }
+
+    /// Normalize target URLs before shortening them, using [`normalize_url`].
+    ///
+    /// Off by default. Normalizing means effectively identical URLs (differing only in host
+    /// casing, duplicate slashes or, optionally, `utm_*` parameters) map to the same target.
+    ///
+    /// Requires the `url` feature to be enabled.
+    #[cfg(feature = "url")]
+    pub fn with_url_normalization(mut self, options: NormalizeOptions) -> Self {
+        self.inner_mut().url_normalization = Some(options);
+        self
+    }
+
+    /// Percent-encode a target URL, then apply the configured URL normalization, if any.
+    #[cfg(feature = "url")]
+    fn prepare_target(&self, url: String) -> Result<String, UrlShortenerError> {
+        let url = percent_encode_url(&url);
+        match self.inner.url_normalization {
+            Some(ref options) => normalize_url(&url, options).ok_or(UrlShortenerError::Validation(
+                ValidationError::InvalidUrlFormat(url),
+            )),
+            None => Ok(url),
+        }
+    }
+
+    /// Percent-encode a target URL.
+    #[cfg(not(feature = "url"))]
+    fn prepare_target(&self, url: String) -> Result<String, UrlShortenerError> {
+        Ok(percent_encode_url(&url))
+    }
+
+    /// Check `max_clicks` against the configured limit, if any.
+    fn check_max_clicks_limit(&self, max_clicks: Option<u32>) -> Result<(), ValidationError> {
+        match (max_clicks, self.inner.max_clicks_limit) {
+            (Some(max_clicks), Some(limit)) if max_clicks > limit => {
+                Err(ValidationError::MaxClicksAboveLimit { max_clicks, limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fresh stats for `req` from the stats cache, if enabled. Requests with a password are never
+    /// answered from the cache.
+    fn cached_stats(&self, req: &StatsRequest) -> Option<StatsResponse> {
+        if req.password.is_some() {
+            return None;
+        }
+        self.inner.stats_cache.as_ref()?.get(&req.short_code)
+    }
+
+    /// Store the `stats` of `req` in the stats cache, if enabled, unless it carries a password.
+    fn cache_stats(&self, req: &StatsRequest, stats: &StatsResponse) {
+        if req.password.is_some() {
+            return;
+        }
+        if let Some(cache) = &self.inner.stats_cache {
+            cache.insert(&req.short_code, stats);
+        }
+    }
+
+    /// The full URL of the API route `path`, relative to the base URL.
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.inner.base_url, path)
+    }
+
+    /// Mark a header as sensitive, so its value is never exposed in logs or debug output.
+    ///
+    /// `Authorization`, `Proxy-Authorization` and `X-Api-Key` are sensitive by default.
+    pub fn with_sensitive_header(mut self, name: HeaderName) -> Self {
+        let inner = self.inner_mut();
+        if !inner.sensitive_headers.contains(&name) {
+            inner.sensitive_headers.push(name);
         }
+        self
+    }
+
+    /// Whether the value of the header `name` is redacted by this client.
+    pub fn is_sensitive_header(&self, name: &HeaderName) -> bool {
+        self.inner.sensitive_headers.contains(name)
+    }
+
+    /// Copy `headers`, replacing the values of sensitive headers with `[REDACTED]`.
+    ///
+    /// Useful for logging requests made alongside the client without leaking credentials.
+    pub fn redact_headers(&self, headers: &HeaderMap) -> HeaderMap {
+        let mut redacted = headers.clone();
+        for (name, value) in redacted.iter_mut() {
+            if self.is_sensitive_header(name) {
+                *value = HeaderValue::from_static("[REDACTED]");
+                value.set_sensitive(true);
+            }
+        }
+        redacted
+    }
+
+    /// Merge the default headers, the headers of the header provider and the `headers` of a single
+    /// request, each taking precedence over the ones before.
+    fn request_headers(&self, headers: HeaderMap) -> HeaderMap {
+        let mut merged = self.inner.default_headers.clone();
+        if let Some(provider) = &self.inner.header_provider {
+            let mut provided = provider();
+            provided.remove(ACCEPT);
+            provided.remove(CONTENT_TYPE);
+            merged.extend(provided);
+        }
+        merged.extend(headers);
+        self.mark_sensitive(merged)
+    }
+
+    /// The headers to send to `url`: the client's default and provided headers if it's on the
+    /// base URL, and none otherwise, so they don't leak to other hosts.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn headers_for(&self, url: &str) -> HeaderMap {
+        if is_on_host(url, &self.inner.base_url) {
+            self.request_headers(HeaderMap::new())
+        } else {
+            HeaderMap::new()
+        }
+    }
+
+    /// Flag the values of sensitive headers so the HTTP stack never prints them.
+    fn mark_sensitive(&self, mut headers: HeaderMap) -> HeaderMap {
+        for (name, value) in headers.iter_mut() {
+            if self.is_sensitive_header(name) {
+                value.set_sensitive(true);
+            }
+        }
+        headers
+    }
+
+    /// How long to wait before retrying after `err`.
+    ///
+    /// A `Retry-After` sent by the server is honored, up to the policy's maximum delay.
+    fn retry_delay(&self, retries: u32, err: &ApiError) -> Duration {
+        let delay = self.inner.retry_policy.delay_for(retries);
+        match err {
+            ApiError::RateLimitExceeded {
+                retry_after: Some(retry_after),
+            } => delay
+                .max(*retry_after)
+                .min(self.inner.retry_policy.max_delay),
+            _ => delay,
+        }
+    }
+
+    /// Run `attempt` until it succeeds, fails with a non-retryable error or runs out of retries.
+    ///
+    /// With the `tracing` or `metrics` features, the whole call is recorded as a request to
+    /// `endpoint`.
+    #[cfg(not(feature = "blocking"))]
+    async fn retrying<T, F, Fut>(
+        &self,
+        endpoint: &'static str,
+        short_code: Option<&str>,
+        mut attempt: F,
+    ) -> Result<T, UrlShortenerError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, UrlShortenerError>>,
+    {
+        traced(endpoint, short_code, async move {
+            let mut retries = 0;
+            let mut transient_retries = 0;
+            loop {
+                let result = attempt().await;
+                self.notify_rate_limit(&result);
+                match result {
+                    Err(UrlShortenerError::Api(ref err))
+                        if retries < self.inner.retry_policy.max_retries
+                            && (self.inner.api_retry)(err) =>
+                    {
+                        sleep(self.retry_delay(retries, err)).await;
+                        retries += 1;
+                    }
+                    Err(ref err)
+                        if transient_retries < self.inner.retry_policy.max_transient_retries
+                            && err.is_transient() =>
+                    {
+                        sleep(self.inner.retry_policy.delay_for(transient_retries)).await;
+                        transient_retries += 1;
+                    }
+                    result => return result,
+                }
+            }
+        })
+        .await
+    }
+
+    /// Run `attempt` until it succeeds, fails with a non-retryable error or runs out of retries.
+    ///
+    /// With the `tracing` or `metrics` features, the whole call is recorded as a request to
+    /// `endpoint`.
+    #[cfg(feature = "blocking")]
+    fn retrying_blocking<T, F>(
+        &self,
+        endpoint: &'static str,
+        short_code: Option<&str>,
+        mut attempt: F,
+    ) -> Result<T, UrlShortenerError>
+    where
+        F: FnMut() -> Result<T, UrlShortenerError>,
+    {
+        traced_blocking(endpoint, short_code, || {
+            let mut retries = 0;
+            let mut transient_retries = 0;
+            loop {
+                let result = attempt();
+                self.notify_rate_limit(&result);
+                match result {
+                    Err(UrlShortenerError::Api(ref err))
+                        if retries < self.inner.retry_policy.max_retries
+                            && (self.inner.api_retry)(err) =>
+                    {
+                        std::thread::sleep(self.retry_delay(retries, err));
+                        retries += 1;
+                    }
+                    Err(ref err)
+                        if transient_retries < self.inner.retry_policy.max_transient_retries
+                            && err.is_transient() =>
+                    {
+                        std::thread::sleep(self.inner.retry_policy.delay_for(transient_retries));
+                        transient_retries += 1;
+                    }
+                    result => return result,
+                }
+            }
+        })
+    }
+
+    /// Shorten a URL (async mode).
+    ///
+    /// The URL is percent-encoded first if it contains spaces, non-ASCII text or other characters
+    /// that must be escaped (see [`percent_encode_url`]), so the `original_url` echoed back may
+    /// differ from the URL given.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn shorten(&self, req: ShortenRequest) -> Result<ShortenResponse, UrlShortenerError> {
+        let (response, _) = self.shorten_with_headers(req).await?;
+        Ok(response)
+    }
+
+    /// Shorten a URL, also returning the headers of the response (async mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`shorten`](Self::shorten).
+    #[cfg(not(feature = "blocking"))]
+    pub async fn shorten_with_headers(
+        &self,
+        req: ShortenRequest,
+    ) -> Result<(ShortenResponse, HeaderMap), UrlShortenerError> {
+        let req = ShortenRequest {
+            url: self.prepare_target(req.url)?,
+            max_clicks: req.max_clicks.or(self.inner.default_max_clicks),
+            block_bots: req.block_bots.or(self.inner.default_block_bots),
+            ..req
+        };
+
+        #[cfg(feature = "custom_url")]
+        req.validate(&self.inner.base_url)?;
+        #[cfg(not(feature = "custom_url"))]
+        req.validate()?;
+        self.check_max_clicks_limit(req.max_clicks)?;
+
+        let req = &req;
+        self.retrying("shorten", req.alias.as_deref(), || async move {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint(""))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_timeout(request, req.timeout).form(req).send().await?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text().await?;
+            if !status.is_success() {
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok((deserialize_success(&text)?, headers))
+        })
+        .await
+    }
+
+    /// Shorten a URL, aborting the request if `token` is cancelled first (async mode).
+    ///
+    /// Returns [`UrlShortenerError::Cancelled`] if cancelled. Otherwise the same as
+    /// [`shorten`](Self::shorten).
+    ///
+    /// Requires the `cancellation` feature to be enabled.
+    #[cfg(all(feature = "cancellation", not(feature = "blocking")))]
+    pub async fn shorten_with_cancellation(
+        &self,
+        req: ShortenRequest,
+        token: &CancellationToken,
+    ) -> Result<ShortenResponse, UrlShortenerError> {
+        cancellable(token, self.shorten(req)).await
+    }
+
+    /// Shorten a URL (blocking mode).
+    ///
+    /// The URL is percent-encoded first if it contains spaces, non-ASCII text or other characters
+    /// that must be escaped (see [`percent_encode_url`]), so the `original_url` echoed back may
+    /// differ from the URL given.
+    #[cfg(feature = "blocking")]
+    pub fn shorten_blocking(
+        &self,
+        req: ShortenRequest,
+    ) -> Result<ShortenResponse, UrlShortenerError> {
+        let (response, _) = self.shorten_with_headers_blocking(req)?;
+        Ok(response)
+    }
+
+    /// Shorten a URL, also returning the headers of the response (blocking mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`shorten_blocking`](Self::shorten_blocking).
+    #[cfg(feature = "blocking")]
+    pub fn shorten_with_headers_blocking(
+        &self,
+        req: ShortenRequest,
+    ) -> Result<(ShortenResponse, HeaderMap), UrlShortenerError> {
+        let req = ShortenRequest {
+            url: self.prepare_target(req.url)?,
+            max_clicks: req.max_clicks.or(self.inner.default_max_clicks),
+            block_bots: req.block_bots.or(self.inner.default_block_bots),
+            ..req
+        };
+
+        #[cfg(feature = "custom_url")]
+        req.validate(&self.inner.base_url)?;
+        #[cfg(not(feature = "custom_url"))]
+        req.validate()?;
+        self.check_max_clicks_limit(req.max_clicks)?;
+
+        let req = &req;
+        self.retrying_blocking("shorten", req.alias.as_deref(), || {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint(""))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_timeout(request, req.timeout).form(req).send()?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text()?;
+            if !status.is_success() {
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok((deserialize_success(&text)?, headers))
+        })
+    }
+
+    /// Shorten a URL with the default options, returning just the short URL (async mode).
+    ///
+    /// A shorthand for quick scripts; use [`shorten`](Self::shorten) to set options or read the
+    /// rest of the response.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn shorten_simple(&self, url: &str) -> Result<String, UrlShortenerError> {
+        let response = self.shorten(ShortenRequest::new(url)).await?;
+        Ok(response.short_url)
+    }
+
+    /// Shorten a URL with the default options, returning just the short URL (blocking mode).
+    ///
+    /// See [`shorten_simple`](Self::shorten_simple).
+    #[cfg(feature = "blocking")]
+    pub fn shorten_simple_blocking(&self, url: &str) -> Result<String, UrlShortenerError> {
+        let response = self.shorten_blocking(ShortenRequest::new(url))?;
+        Ok(response.short_url)
+    }
+
+    /// Shorten many URLs concurrently (async mode).
+    ///
+    /// At most `concurrency` requests are in flight at once (at least one). Each request goes
+    /// through [`shorten`](Self::shorten), including its validation and retry policy, and a
+    /// failing request doesn't stop the others. The results are in the same order as
+    /// `requests`.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn shorten_many(
+        &self,
+        requests: Vec<ShortenRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<ShortenResponse, UrlShortenerError>> {
+        stream::iter(requests)
+            .map(|req| self.shorten(req))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Shorten many URLs concurrently (blocking mode).
+    ///
+    /// The requests are spread over at most `concurrency` threads (at least one). Each request
+    /// goes through [`shorten_blocking`](Self::shorten_blocking), including its validation and
+    /// retry policy, and a failing request doesn't stop the others. The results are in the same
+    /// order as `requests`.
+    #[cfg(feature = "blocking")]
+    pub fn shorten_many_blocking(
+        &self,
+        requests: Vec<ShortenRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<ShortenResponse, UrlShortenerError>> {
+        let workers = concurrency.max(1).min(requests.len());
+        let queue = Mutex::new(requests.into_iter().enumerate());
+        let mut results: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                            let Some((index, req)) = next else {
+                                return done;
+                            };
+                            done.push((index, self.shorten_blocking(req)));
+                        }
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("shorten worker panicked"))
+                .collect()
+        });
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Shorten a URL, then fetch the stats of the new short URL to confirm it exists (async mode).
+    ///
+    /// The stats request uses the short code from the response and the password of `req`, if
+    /// any, and bypasses the stats cache. As a new short URL may take a moment to show up, a
+    /// stats request failing because the short URL isn't found ([`ApiError::UrlError`] or a `404`
+    /// status) is retried once after [`STATS_RETRY_DELAY`]. Other errors are returned right away.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn shorten_then_stats(
+        &self,
+        req: ShortenRequest,
+    ) -> Result<(ShortenResponse, StatsResponse), UrlShortenerError> {
+        let password = req.password.clone();
+        let shortened = self.shorten(req).await?;
+        let stats_req = stats_request_for(&shortened, password)?;
+        let (stats, _) = match self.stats_with_headers(stats_req.clone()).await {
+            Err(err) if is_not_found(&err) => {
+                sleep(STATS_RETRY_DELAY).await;
+                self.stats_with_headers(stats_req).await?
+            }
+            stats => stats?,
+        };
+        Ok((shortened, stats))
+    }
+
+    /// Shorten a URL, then fetch the stats of the new short URL to confirm it exists (blocking
+    /// mode).
+    ///
+    /// See [`shorten_then_stats`](Self::shorten_then_stats) for how the stats are fetched.
+    #[cfg(feature = "blocking")]
+    pub fn shorten_then_stats_blocking(
+        &self,
+        req: ShortenRequest,
+    ) -> Result<(ShortenResponse, StatsResponse), UrlShortenerError> {
+        let password = req.password.clone();
+        let shortened = self.shorten_blocking(req)?;
+        let stats_req = stats_request_for(&shortened, password)?;
+        let (stats, _) = match self.stats_with_headers_blocking(stats_req.clone()) {
+            Err(err) if is_not_found(&err) => {
+                std::thread::sleep(STATS_RETRY_DELAY);
+                self.stats_with_headers_blocking(stats_req)?
+            }
+            stats => stats?,
+        };
+        Ok((shortened, stats))
+    }
+
+    /// Create an emoji URL (async mode).
+    ///
+    /// The URL is percent-encoded first if it contains spaces, non-ASCII text or other characters
+    /// that must be escaped (see [`percent_encode_url`]), so the `original_url` echoed back may
+    /// differ from the URL given.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn emoji(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
+        let (response, _) = self.emoji_with_headers(req).await?;
+        Ok(response)
+    }
+
+    /// Create an emoji URL, also returning the headers of the response (async mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`emoji`](Self::emoji).
+    #[cfg(not(feature = "blocking"))]
+    pub async fn emoji_with_headers(
+        &self,
+        req: EmojiRequest,
+    ) -> Result<(EmojiResponse, HeaderMap), UrlShortenerError> {
+        let req = EmojiRequest {
+            url: self.prepare_target(req.url)?,
+            max_clicks: req.max_clicks.or(self.inner.default_max_clicks),
+            block_bots: req.block_bots.or(self.inner.default_block_bots),
+            ..req
+        };
+
+        #[cfg(feature = "custom_url")]
+        req.validate(&self.inner.base_url)?;
+        #[cfg(not(feature = "custom_url"))]
+        req.validate()?;
+        self.check_max_clicks_limit(req.max_clicks)?;
+
+        let req = &req;
+        self.retrying("emoji", req.emojies.as_deref(), || async move {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint("emoji"))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_timeout(request, req.timeout).form(req).send().await?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text().await?;
+            if !status.is_success() {
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok((deserialize_success(&text)?, headers))
+        })
+        .await
+    }
+
+    /// Create an emoji URL, aborting the request if `token` is cancelled first (async mode).
+    ///
+    /// See [`shorten_with_cancellation`](Self::shorten_with_cancellation).
+    ///
+    /// Requires the `cancellation` feature to be enabled.
+    #[cfg(all(feature = "cancellation", not(feature = "blocking")))]
+    pub async fn emoji_with_cancellation(
+        &self,
+        req: EmojiRequest,
+        token: &CancellationToken,
+    ) -> Result<EmojiResponse, UrlShortenerError> {
+        cancellable(token, self.emoji(req)).await
+    }
+
+    /// Create an emoji URL (blocking mode).
+    ///
+    /// The URL is percent-encoded first if it contains spaces, non-ASCII text or other characters
+    /// that must be escaped (see [`percent_encode_url`]), so the `original_url` echoed back may
+    /// differ from the URL given.
+    #[cfg(feature = "blocking")]
+    pub fn emoji_blocking(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
+        let (response, _) = self.emoji_with_headers_blocking(req)?;
+        Ok(response)
+    }
+
+    /// Create an emoji URL, also returning the headers of the response (blocking mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`emoji_blocking`](Self::emoji_blocking).
+    #[cfg(feature = "blocking")]
+    pub fn emoji_with_headers_blocking(
+        &self,
+        req: EmojiRequest,
+    ) -> Result<(EmojiResponse, HeaderMap), UrlShortenerError> {
+        let req = EmojiRequest {
+            url: self.prepare_target(req.url)?,
+            max_clicks: req.max_clicks.or(self.inner.default_max_clicks),
+            block_bots: req.block_bots.or(self.inner.default_block_bots),
+            ..req
+        };
+
+        #[cfg(feature = "custom_url")]
+        req.validate(&self.inner.base_url)?;
+        #[cfg(not(feature = "custom_url"))]
+        req.validate()?;
+        self.check_max_clicks_limit(req.max_clicks)?;
+
+        let req = &req;
+        self.retrying_blocking("emoji", req.emojies.as_deref(), || {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint("emoji"))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_timeout(request, req.timeout).form(req).send()?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text()?;
+            if !status.is_success() {
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok((deserialize_success(&text)?, headers))
+        })
+    }
+
+    /// Create an emoji URL with the default options, returning just the short URL (async mode).
+    ///
+    /// A shorthand for quick scripts; use [`emoji`](Self::emoji) to set options or read the rest
+    /// of the response.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn emoji_simple(&self, url: &str) -> Result<String, UrlShortenerError> {
+        let response = self.emoji(EmojiRequest::new(url)).await?;
+        Ok(response.short_url)
+    }
+
+    /// Create an emoji URL with the default options, returning just the short URL (blocking
+    /// mode).
+    ///
+    /// See [`emoji_simple`](Self::emoji_simple).
+    #[cfg(feature = "blocking")]
+    pub fn emoji_simple_blocking(&self, url: &str) -> Result<String, UrlShortenerError> {
+        let response = self.emoji_blocking(EmojiRequest::new(url))?;
+        Ok(response.short_url)
+    }
+
+    /// Get statistics for a shortened URL (async mode).
+    #[cfg(not(feature = "blocking"))]
+    pub async fn stats(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
+        req.validate()?;
+        if let Some(stats) = self.cached_stats(&req) {
+            return Ok(stats);
+        }
+        let (stats, _) = self.stats_with_headers(req).await?;
+        Ok(stats)
+    }
+
+    /// Get statistics for a shortened URL, also returning the headers of the response
+    /// (async mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`stats`](Self::stats).
+    ///
+    /// This always sends a request, but still stores the stats in the stats cache, if enabled and
+    /// the request has no password.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn stats_with_headers(
+        &self,
+        req: StatsRequest,
+    ) -> Result<(StatsResponse, HeaderMap), UrlShortenerError> {
+        req.validate()?;
+        let (stats, headers) = self.fetch_stats(&req).await?;
+        self.cache_stats(&req, &stats);
+        Ok((stats, headers))
+    }
+
+    /// Get statistics for a shortened URL, aborting the request if `token` is cancelled first
+    /// (async mode).
+    ///
+    /// See [`shorten_with_cancellation`](Self::shorten_with_cancellation).
+    ///
+    /// Requires the `cancellation` feature to be enabled.
+    #[cfg(all(feature = "cancellation", not(feature = "blocking")))]
+    pub async fn stats_with_cancellation(
+        &self,
+        req: StatsRequest,
+        token: &CancellationToken,
+    ) -> Result<StatsResponse, UrlShortenerError> {
+        cancellable(token, self.stats(req)).await
+    }
+
+    /// Get statistics for a shortened URL as raw JSON, without parsing them into a
+    /// [`StatsResponse`] (async mode).
+    ///
+    /// An escape hatch for when the API returns stats that [`stats`](Self::stats) fails to
+    /// parse, such as after a change to its schema. This always sends a request and bypasses the
+    /// stats cache.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn stats_raw(
+        &self,
+        req: StatsRequest,
+    ) -> Result<serde_json::Value, UrlShortenerError> {
+        req.validate()?;
+        let (stats, _) = self.fetch_stats(&req).await?;
+        Ok(stats)
+    }
+
+    /// Check whether a short URL is password-protected, by requesting its stats without a
+    /// password (async mode).
+    ///
+    /// Returns `Ok(true)` if the API rejects the request with a
+    /// [`PasswordError`](ApiError::PasswordError) or a `401` status, and `Ok(false)` if it
+    /// returns the stats. Short codes that don't exist are an error, as with [`stats`](Self::stats),
+    /// so they can be told apart from protected ones. This always sends a request and bypasses
+    /// the stats cache.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn requires_password(&self, short_code: &str) -> Result<bool, UrlShortenerError> {
+        let req = StatsRequest::new(short_code);
+        req.validate()?;
+        password_required(self.fetch_stats::<serde_json::Value>(&req).await)
+    }
+
+    /// Send a stats request and parse the stats into `T`.
+    #[cfg(not(feature = "blocking"))]
+    async fn fetch_stats<T: DeserializeOwned>(
+        &self,
+        req: &StatsRequest,
+    ) -> Result<(T, HeaderMap), UrlShortenerError> {
+        self.retrying("stats", Some(&req.short_code), || async move {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint(&format!("stats/{}", req.short_code)))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_form(with_timeout(request, req.timeout), req)
+                .send()
+                .await?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text().await?;
+            if !status.is_success() {
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok((deserialize_success(&text)?, headers))
+        })
+        .await
+    }
+
+    /// Get statistics for a shortened URL (blocking mode).
+    #[cfg(feature = "blocking")]
+    pub fn stats_blocking(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
+        req.validate()?;
+        if let Some(stats) = self.cached_stats(&req) {
+            return Ok(stats);
+        }
+        let (stats, _) = self.stats_with_headers_blocking(req)?;
+        Ok(stats)
+    }
+
+    /// Get statistics for a shortened URL, also returning the headers of the response
+    /// (blocking mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`stats_blocking`](Self::stats_blocking).
+    ///
+    /// This always sends a request, but still stores the stats in the stats cache, if enabled and
+    /// the request has no password.
+    #[cfg(feature = "blocking")]
+    pub fn stats_with_headers_blocking(
+        &self,
+        req: StatsRequest,
+    ) -> Result<(StatsResponse, HeaderMap), UrlShortenerError> {
+        req.validate()?;
+        let (stats, headers) = self.fetch_stats_blocking(&req)?;
+        self.cache_stats(&req, &stats);
+        Ok((stats, headers))
+    }
+
+    /// Get statistics for a shortened URL as raw JSON, without parsing them into a
+    /// [`StatsResponse`] (blocking mode).
+    ///
+    /// See [`stats_raw`](Self::stats_raw).
+    #[cfg(feature = "blocking")]
+    pub fn stats_raw_blocking(
+        &self,
+        req: StatsRequest,
+    ) -> Result<serde_json::Value, UrlShortenerError> {
+        req.validate()?;
+        let (stats, _) = self.fetch_stats_blocking(&req)?;
+        Ok(stats)
+    }
+
+    /// Check whether a short URL is password-protected, by requesting its stats without a
+    /// password (blocking mode).
+    ///
+    /// See [`requires_password`](Self::requires_password).
+    #[cfg(feature = "blocking")]
+    pub fn requires_password_blocking(&self, short_code: &str) -> Result<bool, UrlShortenerError> {
+        let req = StatsRequest::new(short_code);
+        req.validate()?;
+        password_required(self.fetch_stats_blocking::<serde_json::Value>(&req))
+    }
+
+    /// Send a stats request and parse the stats into `T` (blocking mode).
+    #[cfg(feature = "blocking")]
+    fn fetch_stats_blocking<T: DeserializeOwned>(
+        &self,
+        req: &StatsRequest,
+    ) -> Result<(T, HeaderMap), UrlShortenerError> {
+        self.retrying_blocking("stats", Some(&req.short_code), || {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint(&format!("stats/{}", req.short_code)))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_form(with_timeout(request, req.timeout), req).send()?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text()?;
+            if !status.is_success() {
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok((deserialize_success(&text)?, headers))
+        })
+    }
+
+    /// Export data for a shortened URL (async mode).
+    #[cfg(not(feature = "blocking"))]
+    pub async fn export(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError> {
+        let (response, _) = self.export_with_headers(req).await?;
+        Ok(response)
+    }
+
+    /// Export data for a shortened URL, also returning the headers of the response (async mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`export`](Self::export).
+    #[cfg(not(feature = "blocking"))]
+    pub async fn export_with_headers(
+        &self,
+        req: ExportRequest,
+    ) -> Result<(ExportResponse, HeaderMap), UrlShortenerError> {
+        let resp = self.export_response(&req).await?;
+        let headers = resp.headers().clone();
+        let content_type = content_type(&headers);
+        let data = resp.bytes().await?;
+        let export = ExportResponse {
+            data: data.to_vec(),
+            format: Some(req.export_format),
+            content_type,
+        };
+        Ok((export, headers))
+    }
+
+    /// Export data for a shortened URL, aborting the request if `token` is cancelled first
+    /// (async mode).
+    ///
+    /// See [`shorten_with_cancellation`](Self::shorten_with_cancellation).
+    ///
+    /// Requires the `cancellation` feature to be enabled.
+    #[cfg(all(feature = "cancellation", not(feature = "blocking")))]
+    pub async fn export_with_cancellation(
+        &self,
+        req: ExportRequest,
+        token: &CancellationToken,
+    ) -> Result<ExportResponse, UrlShortenerError> {
+        cancellable(token, self.export(req)).await
+    }
+
+    /// Export a shortened URL in every [`ExportFormat`] concurrently (async mode).
+    ///
+    /// Each export goes through [`export`](Self::export), including its validation and retry
+    /// policy, and a failing export doesn't stop the others, so there is a result for every
+    /// format.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn export_all(
+        &self,
+        short_code: &str,
+        password: Option<&str>,
+    ) -> HashMap<ExportFormat, Result<ExportResponse, UrlShortenerError>> {
+        let exports = ExportFormat::all().map(|format| async move {
+            let result = self
+                .export(export_request(short_code, password, &format))
+                .await;
+            (format, result)
+        });
+        future::join_all(exports).await.into_iter().collect()
+    }
+
+    /// Export data for a shortened URL straight into `writer` (async mode).
+    ///
+    /// The response body is written chunk by chunk as it arrives instead of being buffered in
+    /// memory, which suits large exports. Returns the number of bytes written. Failed requests are
+    /// retried according to the retry policy, but once data has been written the export is not
+    /// retried.
+    ///
+    /// On wasm32 targets, where the body can't be read in chunks, it is buffered first.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn export_to_writer<W>(
+        &self,
+        req: ExportRequest,
+        writer: &mut W,
+    ) -> Result<u64, UrlShortenerError>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let written = {
+            let mut resp = self.export_response(&req).await?;
+            let mut written = 0;
+            while let Some(chunk) = resp.chunk().await? {
+                writer.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+            }
+            written
+        };
+        #[cfg(target_arch = "wasm32")]
+        let written = {
+            let data = self.export_response(&req).await?.bytes().await?;
+            writer.write_all(&data).await?;
+            data.len() as u64
+        };
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    /// Validate and send an export request, returning the successful response.
+    #[cfg(not(feature = "blocking"))]
+    async fn export_response(
+        &self,
+        req: &ExportRequest,
+    ) -> Result<reqwest::Response, UrlShortenerError> {
+        req.validate()?;
+
+        self.retrying("export", Some(&req.short_code), || async move {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint(&format!("export/{}/{}", req.short_code, req.export_format)))
+                .headers(self.request_headers(req.headers()));
+            let resp = with_form(with_timeout(request, req.timeout), req)
+                .send()
+                .await?;
+
+            let status = resp.status();
+            record_status(status);
+            if !status.is_success() {
+                let headers = resp.headers().clone();
+                let text = resp.text().await?;
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok(resp)
+        })
+        .await
+    }
 
-        let data = resp.bytes().map_err(UrlShortenerError::Http)?;
-        let result = ExportResponse {
+    /// Export data for a shortened URL (blocking mode).
+    #[cfg(feature = "blocking")]
+    pub fn export_blocking(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError> {
+        let (response, _) = self.export_with_headers_blocking(req)?;
+        Ok(response)
+    }
+
+    /// Export data for a shortened URL, also returning the headers of the response (blocking mode).
+    ///
+    /// Useful for reading headers set by self-hosted deployments, such as
+    /// `X-RateLimit-Remaining` or a request ID. Otherwise the same as
+    /// [`export_blocking`](Self::export_blocking).
+    #[cfg(feature = "blocking")]
+    pub fn export_with_headers_blocking(
+        &self,
+        req: ExportRequest,
+    ) -> Result<(ExportResponse, HeaderMap), UrlShortenerError> {
+        let resp = self.export_response_blocking(&req)?;
+        let headers = resp.headers().clone();
+        let content_type = content_type(&headers);
+        let data = resp.bytes()?;
+        let export = ExportResponse {
             data: data.to_vec(),
+            format: Some(req.export_format),
+            content_type,
         };
+        Ok((export, headers))
+    }
+
+    /// Export a shortened URL in every [`ExportFormat`] concurrently (blocking mode).
+    ///
+    /// Each export runs on its own thread and goes through
+    /// [`export_blocking`](Self::export_blocking), including its validation and retry policy. A
+    /// failing export doesn't stop the others, so there is a result for every format.
+    #[cfg(feature = "blocking")]
+    pub fn export_all_blocking(
+        &self,
+        short_code: &str,
+        password: Option<&str>,
+    ) -> HashMap<ExportFormat, Result<ExportResponse, UrlShortenerError>> {
+        thread::scope(|scope| {
+            let handles = ExportFormat::all().map(|format| {
+                scope.spawn(move || {
+                    let result =
+                        self.export_blocking(export_request(short_code, password, &format));
+                    (format, result)
+                })
+            });
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("export worker panicked"))
+                .collect()
+        })
+    }
+
+    /// Export data for a shortened URL straight into `writer` (blocking mode).
+    ///
+    /// The response body is copied into the writer as it arrives instead of being buffered in
+    /// memory, which suits large exports. Returns the number of bytes written. Failed requests are
+    /// retried according to the retry policy, but once data has been written the export is not
+    /// retried.
+    #[cfg(feature = "blocking")]
+    pub fn export_to_writer_blocking<W: std::io::Write>(
+        &self,
+        req: ExportRequest,
+        writer: &mut W,
+    ) -> Result<u64, UrlShortenerError> {
+        let mut resp = self.export_response_blocking(&req)?;
+        let written = resp.copy_to(writer)?;
+        writer.flush()?;
+        Ok(written)
+    }
+
+    /// Validate and send an export request, returning the successful response.
+    #[cfg(feature = "blocking")]
+    fn export_response_blocking(
+        &self,
+        req: &ExportRequest,
+    ) -> Result<reqwest::blocking::Response, UrlShortenerError> {
+        req.validate()?;
+
+        self.retrying_blocking("export", Some(&req.short_code), || {
+            let request = self
+                .inner
+                .client
+                .post(self.endpoint(&format!("export/{}/{}", req.short_code, req.export_format)))
+                .headers(self.request_headers(req.headers()));
+            let resp = with_form(with_timeout(request, req.timeout), req).send()?;
+
+            let status = resp.status();
+            record_status(status);
+            if !status.is_success() {
+                let headers = resp.headers().clone();
+                let text = resp.text()?;
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok(resp)
+        })
+    }
+
+    /// Delete a shortened URL (async mode).
+    ///
+    /// Sends `DELETE /{short_code}`, which is only supported by self-hosted instances, so this
+    /// requires the `custom_url` feature to be enabled.
+    #[cfg(all(feature = "custom_url", not(feature = "blocking")))]
+    pub async fn delete(&self, req: DeleteRequest) -> Result<(), UrlShortenerError> {
+        req.validate()?;
+
+        let req = &req;
+        self.retrying("delete", Some(&req.short_code), || async move {
+            let request = self
+                .inner
+                .client
+                .delete(self.endpoint(&req.short_code))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_form(with_timeout(request, req.timeout), req)
+                .send()
+                .await?;
+
+            let status = resp.status();
+            record_status(status);
+            if !status.is_success() {
+                let headers = resp.headers().clone();
+                let text = resp.text().await?;
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Delete a shortened URL (blocking mode).
+    ///
+    /// Sends `DELETE /{short_code}`, which is only supported by self-hosted instances, so this
+    /// requires the `custom_url` feature to be enabled.
+    #[cfg(all(feature = "custom_url", feature = "blocking"))]
+    pub fn delete_blocking(&self, req: DeleteRequest) -> Result<(), UrlShortenerError> {
+        req.validate()?;
+
+        let req = &req;
+        self.retrying_blocking("delete", Some(&req.short_code), || {
+            let request = self
+                .inner
+                .client
+                .delete(self.endpoint(&req.short_code))
+                .header("Accept", "application/json")
+                .headers(self.request_headers(req.headers()));
+            let resp = with_form(with_timeout(request, req.timeout), req).send()?;
+
+            let status = resp.status();
+            record_status(status);
+            if !status.is_success() {
+                let headers = resp.headers().clone();
+                let text = resp.text()?;
+                return Err(parse_error(status, &headers, &text));
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Render a QR code of the short URL for `short_code`.
+    ///
+    /// The spoo.me API has no QR code route, so the code is rendered locally from
+    /// `{base_url}/{short_code}` and no request is sent.
+    ///
+    /// Requires the `qr` feature to be enabled.
+    #[cfg(feature = "qr")]
+    pub fn qr(
+        &self,
+        short_code: &str,
+        options: &QrOptions,
+    ) -> Result<QrResponse, UrlShortenerError> {
+        if !is_valid_short_code(short_code) {
+            return Err(ValidationError::InvalidAliasFormat(short_code.to_string()).into());
+        }
+        QrResponse::render(&self.endpoint(short_code), options)
+    }
+
+    /// Look up where a short URL points without following its redirect (async mode).
+    ///
+    /// Sends `GET /{short_code}`, or `POST /{short_code}` with the password if one is given, and
+    /// reads the `Location` of the redirect. A password-protected link answers with a password
+    /// page instead, so it resolves to no URL when requested without a password, and to an
+    /// [`ApiError::PasswordError`] when the password is wrong. The server may count this request
+    /// as a click, just like following the short URL.
+    ///
+    /// Not available on wasm32 targets, where the browser always follows redirects itself.
+    #[cfg(all(not(feature = "blocking"), not(target_arch = "wasm32")))]
+    pub async fn resolve(&self, req: ResolveRequest) -> Result<ResolveResponse, UrlShortenerError> {
+        req.validate()?;
+
+        let client = self.no_redirect_client()?;
+        let req = &req;
+        self.retrying("resolve", Some(&req.short_code), || async {
+            let url = self.endpoint(&req.short_code);
+            let builder = match req.password {
+                Some(_) => with_form(client.post(url), req),
+                None => client.get(url),
+            };
+            let resp = with_timeout(builder, req.timeout)
+                .headers(self.request_headers(req.headers()))
+                .send()
+                .await?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text().await?;
+            resolved(req, status, &headers, &text)
+        })
+        .await
+    }
+
+    /// Look up where a short URL points without following its redirect (blocking mode).
+    ///
+    /// Sends `GET /{short_code}`, or `POST /{short_code}` with the password if one is given, and
+    /// reads the `Location` of the redirect. A password-protected link answers with a password
+    /// page instead, so it resolves to no URL when requested without a password, and to an
+    /// [`ApiError::PasswordError`] when the password is wrong. The server may count this request
+    /// as a click, just like following the short URL.
+    #[cfg(feature = "blocking")]
+    pub fn resolve_blocking(
+        &self,
+        req: ResolveRequest,
+    ) -> Result<ResolveResponse, UrlShortenerError> {
+        req.validate()?;
+
+        let client = self.no_redirect_client()?;
+        let req = &req;
+        self.retrying_blocking("resolve", Some(&req.short_code), || {
+            let url = self.endpoint(&req.short_code);
+            let builder = match req.password {
+                Some(_) => with_form(client.post(url), req),
+                None => client.get(url),
+            };
+            let resp = with_timeout(builder, req.timeout)
+                .headers(self.request_headers(req.headers()))
+                .send()?;
+
+            let status = resp.status();
+            record_status(status);
+            let headers = resp.headers().clone();
+            let text = resp.text()?;
+            resolved(req, status, &headers, &text)
+        })
+    }
+
+    /// An HTTP client with the same settings as this one, but which doesn't follow redirects.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn no_redirect_client(&self) -> Result<HttpClient, UrlShortenerError> {
+        let mut builder = self.inner.connection.apply(
+            ReqwestClient::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .user_agent(&self.inner.user_agent),
+        );
+        if let Some(timeout) = self.inner.timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(http_client(builder.build()?))
+    }
+
+    /// Follow a short URL to where it lands (async mode).
+    ///
+    /// Unlike [`resolve`](Self::resolve), which only reads the first redirect, this sends
+    /// `GET /{short_code}` and follows every redirect, up to [`MAX_REDIRECTS`] of them. The result
+    /// holds each URL visited and the status of the last response, which may be an error page of
+    /// the target site. If the short URL itself answers with an error instead of redirecting, the
+    /// error is returned. The server counts this request as a click.
+    ///
+    /// Each redirect is followed by hand, with a separate HTTP client built once per call from this
+    /// client's settings, so a client injected with [`with_client`](Self::with_client) or
+    /// `with_middleware_client` isn't used. The client's default and provided headers are only
+    /// sent to URLs on the base URL's host, so they don't leak to the target site.
+    ///
+    /// Not available on wasm32 targets, where the browser always follows redirects itself.
+    #[cfg(all(not(feature = "blocking"), not(target_arch = "wasm32")))]
+    pub async fn follow(&self, short_code: &str) -> Result<FollowResult, UrlShortenerError> {
+        if !is_valid_short_code(short_code) {
+            return Err(ValidationError::InvalidAliasFormat(short_code.to_string()).into());
+        }
 
-        Ok(result)
+        let client = self.no_redirect_client()?;
+        self.retrying("follow", Some(short_code), || async {
+            let mut url = self.endpoint(short_code);
+            let mut chain = vec![url.clone()];
+            loop {
+                let resp = client
+                    .get(&url)
+                    .headers(self.headers_for(&url))
+                    .send()
+                    .await?;
+
+                let status = resp.status();
+                record_status(status);
+                if chain.len() == 1 && !status.is_success() && !status.is_redirection() {
+                    let headers = resp.headers().clone();
+                    let text = resp.text().await?;
+                    return Err(parse_error(status, &headers, &text));
+                }
+                match redirect_target(&url, status, resp.headers()) {
+                    Some(next) if chain.len() <= MAX_REDIRECTS => {
+                        chain.push(next.clone());
+                        url = next;
+                    }
+                    _ => return Ok(followed(chain, status)),
+                }
+            }
+        })
+        .await
     }
+
+    /// Follow a short URL to where it lands (blocking mode).
+    ///
+    /// Unlike [`resolve_blocking`](Self::resolve_blocking), which only reads the first redirect,
+    /// this sends `GET /{short_code}` and follows every redirect, up to [`MAX_REDIRECTS`] of them.
+    /// The result holds each URL visited and the status of the last response, which may be an
+    /// error page of the target site. If the short URL itself answers with an error instead of
+    /// redirecting, the error is returned. The server counts this request as a click.
+    ///
+    /// Each redirect is followed by hand, with a separate HTTP client built once per call from this
+    /// client's settings, so a client injected with
+    /// [`with_blocking_client`](Self::with_blocking_client) isn't used. The client's default and
+    /// provided headers are only sent to URLs on the base URL's host, so they don't leak to the
+    /// target site.
+    #[cfg(feature = "blocking")]
+    pub fn follow_blocking(&self, short_code: &str) -> Result<FollowResult, UrlShortenerError> {
+        if !is_valid_short_code(short_code) {
+            return Err(ValidationError::InvalidAliasFormat(short_code.to_string()).into());
+        }
+
+        let client = self.no_redirect_client()?;
+        self.retrying_blocking("follow", Some(short_code), || {
+            let mut url = self.endpoint(short_code);
+            let mut chain = vec![url.clone()];
+            loop {
+                let resp = client.get(&url).headers(self.headers_for(&url)).send()?;
+
+                let status = resp.status();
+                record_status(status);
+                if chain.len() == 1 && !status.is_success() && !status.is_redirection() {
+                    let headers = resp.headers().clone();
+                    let text = resp.text()?;
+                    return Err(parse_error(status, &headers, &text));
+                }
+                match redirect_target(&url, status, resp.headers()) {
+                    Some(next) if chain.len() <= MAX_REDIRECTS => {
+                        chain.push(next.clone());
+                        url = next;
+                    }
+                    _ => return Ok(followed(chain, status)),
+                }
+            }
+        })
+    }
+
+    /// Check that the instance is reachable and accepting requests (async mode).
+    ///
+    /// Sends `GET /` for the home page, which creates nothing and counts no clicks. The instance
+    /// is healthy if it answers with a 2xx status. Otherwise the error says what went wrong: an
+    /// [`UrlShortenerError::Http`] error for DNS, TLS, proxy or connection failures, an
+    /// [`ApiError::RateLimitExceeded`] error if the client is being rate limited, and an
+    /// [`UrlShortenerError::Unexpected`] error for any other status.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn health(&self) -> Result<(), UrlShortenerError> {
+        self.retrying("health", None, || async {
+            let request = self
+                .inner
+                .client
+                .get(self.endpoint(""))
+                .headers(self.request_headers(HeaderMap::new()));
+            let resp = request.send().await?;
+
+            let status = resp.status();
+            record_status(status);
+            if status.is_success() {
+                return Ok(());
+            }
+            let headers = resp.headers().clone();
+            let text = resp.text().await?;
+            Err(parse_error(status, &headers, &text))
+        })
+        .await
+    }
+
+    /// Check that the instance is reachable and accepting requests (blocking mode).
+    ///
+    /// Sends `GET /` for the home page, which creates nothing and counts no clicks. The instance
+    /// is healthy if it answers with a 2xx status. Otherwise the error says what went wrong: an
+    /// [`UrlShortenerError::Http`] error for DNS, TLS, proxy or connection failures, an
+    /// [`ApiError::RateLimitExceeded`] error if the client is being rate limited, and an
+    /// [`UrlShortenerError::Unexpected`] error for any other status.
+    #[cfg(feature = "blocking")]
+    pub fn health_blocking(&self) -> Result<(), UrlShortenerError> {
+        self.retrying_blocking("health", None, || {
+            let request = self
+                .inner
+                .client
+                .get(self.endpoint(""))
+                .headers(self.request_headers(HeaderMap::new()));
+            let resp = request.send()?;
+
+            let status = resp.status();
+            record_status(status);
+            if status.is_success() {
+                return Ok(());
+            }
+            let headers = resp.headers().clone();
+            let text = resp.text()?;
+            Err(parse_error(status, &headers, &text))
+        })
+    }
+
+    /// Open a connection to the instance ahead of the first real request (async mode).
+    ///
+    /// Sends `HEAD /`, so DNS resolution and the TCP and TLS handshakes are done and the
+    /// connection is kept in the client's pool, instead of adding to the latency of the first
+    /// API call. This is never done automatically. It counts as one request, including towards
+    /// rate limits. Any response from the instance is fine, so only failing to reach it is an
+    /// error, as with [`health`](Self::health).
+    #[cfg(not(feature = "blocking"))]
+    pub async fn warmup(&self) -> Result<(), UrlShortenerError> {
+        self.retrying("warmup", None, || async {
+            let resp = self
+                .inner
+                .client
+                .head(self.endpoint(""))
+                .headers(self.request_headers(HeaderMap::new()))
+                .send()
+                .await?;
+            record_status(resp.status());
+            Ok(())
+        })
+        .await
+    }
+
+    /// Open a connection to the instance ahead of the first real request (blocking mode).
+    ///
+    /// See [`warmup`](Self::warmup).
+    #[cfg(feature = "blocking")]
+    pub fn warmup_blocking(&self) -> Result<(), UrlShortenerError> {
+        self.retrying_blocking("warmup", None, || {
+            let resp = self
+                .inner
+                .client
+                .head(self.endpoint(""))
+                .headers(self.request_headers(HeaderMap::new()))
+                .send()?;
+            record_status(resp.status());
+            Ok(())
+        })
+    }
+
+    /// Check that `short_url` redirects straight to `expected_target` (async mode).
+    ///
+    /// The redirect is not followed; instead the `Location` header of the 3xx response is
+    /// compared to `expected_target`. Returns `Ok(false)` if the short URL doesn't redirect or
+    /// redirects elsewhere. Note that the server may count this request as a click.
+    ///
+    /// The request is retried like API calls, so a `429` response is a
+    /// [`ApiError::RateLimitExceeded`] error once retries run out. The client's default and
+    /// provided headers are only sent if `short_url` is on the base URL.
+    ///
+    /// Not available on wasm32 targets, where the browser always follows redirects itself.
+    #[cfg(all(not(feature = "blocking"), not(target_arch = "wasm32")))]
+    pub async fn verify_redirect(
+        &self,
+        short_url: &str,
+        expected_target: &str,
+    ) -> Result<bool, UrlShortenerError> {
+        let client = self.no_redirect_client()?;
+        let headers = self.headers_for(short_url);
+        self.retrying("verify_redirect", None, || async {
+            let resp = client
+                .get(short_url)
+                .headers(headers.clone())
+                .send()
+                .await?;
+            verified_redirect(resp.status(), resp.headers(), expected_target)
+        })
+        .await
+    }
+
+    /// Check that `short_url` redirects straight to `expected_target` (blocking mode).
+    ///
+    /// The redirect is not followed; instead the `Location` header of the 3xx response is
+    /// compared to `expected_target`. Returns `Ok(false)` if the short URL doesn't redirect or
+    /// redirects elsewhere. Note that the server may count this request as a click.
+    ///
+    /// See [`verify_redirect`](Self::verify_redirect) for retries and headers.
+    #[cfg(feature = "blocking")]
+    pub fn verify_redirect_blocking(
+        &self,
+        short_url: &str,
+        expected_target: &str,
+    ) -> Result<bool, UrlShortenerError> {
+        let client = self.no_redirect_client()?;
+        let headers = self.headers_for(short_url);
+        self.retrying_blocking("verify_redirect", None, || {
+            let resp = client.get(short_url).headers(headers.clone()).send()?;
+            verified_redirect(resp.status(), resp.headers(), expected_target)
+        })
+    }
+}
+
+/// Wait for `duration` without blocking the thread, on tokio's timer or, on wasm32 targets, the
+/// browser's.
+#[cfg(all(not(feature = "blocking"), not(target_arch = "wasm32")))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Wait for `duration` without blocking the thread, on tokio's timer or, on wasm32 targets, the
+/// browser's.
+#[cfg(all(not(feature = "blocking"), target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Run an API call until `token` is cancelled, dropping it (and so its in-flight request) if that
+/// happens first.
+#[cfg(all(feature = "cancellation", not(feature = "blocking")))]
+async fn cancellable<T, Fut>(token: &CancellationToken, call: Fut) -> Result<T, UrlShortenerError>
+where
+    Fut: std::future::Future<Output = Result<T, UrlShortenerError>>,
+{
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(UrlShortenerError::Cancelled),
+        result = call => result,
+    }
+}
+
+/// Run an API call, inside a span recording its endpoint, short code, HTTP status and elapsed time
+/// when the `tracing` feature is enabled, and counting it and timing it when the `metrics` feature
+/// is enabled.
+#[cfg(not(feature = "blocking"))]
+async fn traced<T, Fut>(
+    endpoint: &'static str,
+    short_code: Option<&str>,
+    call: Fut,
+) -> Result<T, UrlShortenerError>
+where
+    Fut: std::future::Future<Output = Result<T, UrlShortenerError>>,
+{
+    let _ = (endpoint, short_code);
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    let start = Instant::now();
+    #[cfg(feature = "tracing")]
+    let result = {
+        use tracing::Instrument;
+
+        let span = request_span(endpoint, short_code);
+        let result = call.instrument(span.clone()).await;
+        finish_request_span(&span, start, &result);
+        result
+    };
+    #[cfg(not(feature = "tracing"))]
+    let result = call.await;
+    #[cfg(feature = "metrics")]
+    record_request_metrics(endpoint, start, &result);
+    result
+}
+
+/// Run an API call, inside a span recording its endpoint, short code, HTTP status and elapsed time
+/// when the `tracing` feature is enabled, and counting it and timing it when the `metrics` feature
+/// is enabled.
+#[cfg(feature = "blocking")]
+fn traced_blocking<T>(
+    endpoint: &'static str,
+    short_code: Option<&str>,
+    call: impl FnOnce() -> Result<T, UrlShortenerError>,
+) -> Result<T, UrlShortenerError> {
+    let _ = (endpoint, short_code);
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    let start = Instant::now();
+    #[cfg(feature = "tracing")]
+    let result = {
+        let span = request_span(endpoint, short_code);
+        let result = span.in_scope(call);
+        finish_request_span(&span, start, &result);
+        result
+    };
+    #[cfg(not(feature = "tracing"))]
+    let result = call();
+    #[cfg(feature = "metrics")]
+    record_request_metrics(endpoint, start, &result);
+    result
+}
+
+/// Count an API call in `spoo_me_requests_total` and record its duration in
+/// `spoo_me_request_duration_seconds`, both labelled with the endpoint and outcome.
+///
+/// Short codes are left out of the labels, as they would make a new series per URL.
+#[cfg(feature = "metrics")]
+fn record_request_metrics<T>(
+    endpoint: &'static str,
+    start: Instant,
+    result: &Result<T, UrlShortenerError>,
+) {
+    let outcome = match result {
+        Ok(_) => "success",
+        Err(UrlShortenerError::Validation(_)) => "validation_error",
+        Err(UrlShortenerError::Api(_)) => "api_error",
+        Err(err) if err.is_timeout() => "timeout",
+        Err(_) => "error",
+    };
+    metrics::counter!("spoo_me_requests_total", "endpoint" => endpoint, "outcome" => outcome)
+        .increment(1);
+    metrics::histogram!(
+        "spoo_me_request_duration_seconds",
+        "endpoint" => endpoint,
+        "outcome" => outcome,
+    )
+    .record(start.elapsed().as_secs_f64());
+}
+
+/// The span of a single API call. Request bodies, and so passwords, are never recorded.
+#[cfg(feature = "tracing")]
+fn request_span(endpoint: &'static str, short_code: Option<&str>) -> tracing::Span {
+    tracing::info_span!(
+        "spoo_me.request",
+        endpoint,
+        short_code,
+        status = tracing::field::Empty,
+        elapsed_ms = tracing::field::Empty,
+    )
+}
+
+/// Record how long an API call took and emit an event with its outcome.
+#[cfg(feature = "tracing")]
+fn finish_request_span<T>(
+    span: &tracing::Span,
+    start: Instant,
+    result: &Result<T, UrlShortenerError>,
+) {
+    span.record("elapsed_ms", start.elapsed().as_millis() as u64);
+    let _entered = span.enter();
+    match result {
+        Ok(_) => tracing::debug!("request succeeded"),
+        Err(err @ (UrlShortenerError::Api(_) | UrlShortenerError::Validation(_))) => {
+            tracing::warn!(error = %err, "request failed")
+        }
+        Err(err) => tracing::error!(error = %err, "request failed"),
+    }
+}
+
+/// Record the HTTP status of a response on the current request span.
+fn record_status(status: StatusCode) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("status", status.as_u16());
+    #[cfg(not(feature = "tracing"))]
+    let _ = status;
+}
+
+/// Build the result of resolving a short URL from a redirect or password page response.
+///
+/// The short URL asked for a password if it answered with a password page, a
+/// [`PasswordError`](ApiError::PasswordError) or a `401` status. Any other response that isn't a
+/// redirect is an error.
+#[cfg(not(target_arch = "wasm32"))]
+fn resolved(
+    req: &ResolveRequest,
+    status: StatusCode,
+    headers: &HeaderMap,
+    text: &str,
+) -> Result<ResolveResponse, UrlShortenerError> {
+    let location = headers
+        .get(reqwest::header::LOCATION)
+        .filter(|_| status.is_redirection())
+        .and_then(|location| location.to_str().ok());
+    if let Some(url) = location {
+        return Ok(ResolveResponse {
+            url: Some(url.to_string()),
+            password_protected: false,
+        });
+    }
+
+    let err = if status.is_success() {
+        UrlShortenerError::Unexpected {
+            status: status.as_u16(),
+            body: body_snippet(text),
+        }
+    } else {
+        parse_error(status, headers, text)
+    };
+    let asks_password = (status.is_success() && is_password_page(text))
+        || matches!(
+            err,
+            UrlShortenerError::Api(ApiError::PasswordError)
+                | UrlShortenerError::Unexpected { status: 401, .. }
+        );
+    match (asks_password, &req.password) {
+        (true, None) => Ok(ResolveResponse {
+            url: None,
+            password_protected: true,
+        }),
+        (true, Some(_)) if status.is_success() => {
+            Err(UrlShortenerError::Api(ApiError::PasswordError))
+        }
+        _ => Err(err),
+    }
+}
+
+/// Whether `text` is an HTML page with a password field, as shown for password-protected links.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_password_page(text: &str) -> bool {
+    let text = text.to_ascii_lowercase();
+    text.contains("type=\"password\"") || text.contains("type='password'")
+}
+
+/// Whether a response is a redirect whose `Location` is exactly `expected_target`.
+#[cfg(not(target_arch = "wasm32"))]
+fn redirects_to(
+    status: reqwest::StatusCode,
+    location: Option<&reqwest::header::HeaderValue>,
+    expected_target: &str,
+) -> bool {
+    status.is_redirection()
+        && location
+            .and_then(|location| location.to_str().ok())
+            .is_some_and(|location| location == expected_target)
+}
+
+/// Whether a response redirects to `expected_target`, or a rate limit error if it's a `429`.
+#[cfg(not(target_arch = "wasm32"))]
+fn verified_redirect(
+    status: StatusCode,
+    headers: &HeaderMap,
+    expected_target: &str,
+) -> Result<bool, UrlShortenerError> {
+    record_status(status);
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(parse_error(status, headers, ""));
+    }
+    Ok(redirects_to(
+        status,
+        headers.get(reqwest::header::LOCATION),
+        expected_target,
+    ))
+}
+
+/// Whether `err` says the short URL doesn't exist (yet).
+fn is_not_found(err: &UrlShortenerError) -> bool {
+    matches!(
+        err,
+        UrlShortenerError::Api(ApiError::UrlError)
+            | UrlShortenerError::Unexpected { status: 404, .. }
+    )
+}
+
+/// Whether the result of a stats request sent without a password shows the short URL needs one.
+fn password_required<T>(result: Result<T, UrlShortenerError>) -> Result<bool, UrlShortenerError> {
+    match result {
+        Ok(_) => Ok(false),
+        Err(UrlShortenerError::Api(ApiError::PasswordError))
+        | Err(UrlShortenerError::Unexpected { status: 401, .. }) => Ok(true),
+        Err(err) => Err(err),
+    }
+}
+
+/// Turn an unsuccessful response into the matching [`UrlShortenerError`].
+///
+/// A `429` status is a rate limit; otherwise the `error` field of a JSON body names the
+/// [`ApiError`]. Other bodies, such as the HTML error page of a proxy or an empty body, are
+/// returned as [`UrlShortenerError::Unexpected`] along with the status code, cut to
+/// [`ERROR_BODY_SNIPPET_LEN`] characters.
+fn parse_error(status: StatusCode, headers: &HeaderMap, text: &str) -> UrlShortenerError {
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return UrlShortenerError::Api(ApiError::RateLimitExceeded {
+            retry_after: retry_after(headers),
+        });
+    }
+
+    if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(text) {
+        if let Some(err) = err_json.get("error").and_then(|e| e.as_str()) {
+            return UrlShortenerError::Api(match err {
+                "UrlError" => ApiError::UrlError,
+                "AliasError" => ApiError::AliasError,
+                "PasswordError" => ApiError::PasswordError,
+                "MaxClicksError" => ApiError::MaxClicksError,
+                "EmojiError" => ApiError::EmojiError,
+                err => ApiError::Other(err.to_string()),
+            });
+        }
+    }
+    UrlShortenerError::Unexpected {
+        status: status.as_u16(),
+        body: body_snippet(text),
+    }
+}
+
+/// The start of an error body, trimmed and cut to [`ERROR_BODY_SNIPPET_LEN`] characters.
+fn body_snippet(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(ERROR_BODY_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Read the `Retry-After` header, given either in seconds or as an HTTP date.
+///
+/// Dates are resolved relative to the response's `Date` header, or the local time if it has none.
+/// Dates in the past mean the request can be retried right away.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let retry_at = httpdate::parse_http_date(value).ok()?;
+    let now = headers
+        .get(DATE)
+        .and_then(|date| date.to_str().ok())
+        .and_then(|date| httpdate::parse_http_date(date).ok())
+        .unwrap_or_else(now);
+    Some(retry_at.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// The current time, as a [`std::time::SystemTime`] on every target.
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> std::time::SystemTime {
+    SystemTime::now()
+}
+
+/// The current time, as a [`std::time::SystemTime`] on every target.
+#[cfg(target_arch = "wasm32")]
+fn now() -> std::time::SystemTime {
+    SystemTime::now().to_std()
+}
+
+/// Where a response to `url` with `status` and `headers` redirects to, or `None` if it isn't a
+/// redirect with a usable `Location` header.
+#[cfg(not(target_arch = "wasm32"))]
+fn redirect_target(url: &str, status: StatusCode, headers: &HeaderMap) -> Option<String> {
+    if !status.is_redirection() {
+        return None;
+    }
+    let location = headers.get(reqwest::header::LOCATION)?.to_str().ok()?;
+    let target = reqwest::Url::parse(url).ok()?.join(location).ok()?;
+    Some(target.to_string())
+}
+
+/// The result of following the redirects in `chain`, the last of which answered with `status`.
+#[cfg(not(target_arch = "wasm32"))]
+fn followed(chain: Vec<String>, status: StatusCode) -> FollowResult {
+    FollowResult {
+        max_redirects_reached: chain.len() > MAX_REDIRECTS && status.is_redirection(),
+        final_url: chain.last().cloned().unwrap_or_default(),
+        status: status.as_u16(),
+        chain,
+    }
+}
+
+/// Apply the timeout of a single request, which overrides the client's.
+fn with_timeout(request: HttpRequestBuilder, timeout: Option<Duration>) -> HttpRequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+/// Send `req` as a form body, unless it has no fields to send.
+///
+/// Some self-hosted servers reject empty form bodies, so requests such as stats without a password
+/// are sent without a body at all.
+fn with_form<T: Serialize>(request: HttpRequestBuilder, req: &T) -> HttpRequestBuilder {
+    if has_form_fields(req) {
+        request.form(req)
+    } else {
+        request
+    }
+}
+
+/// Whether `req` serializes to a non-empty form. Requests that fail to serialize count as
+/// non-empty, so the error surfaces when the form is built.
+fn has_form_fields<T: Serialize>(req: &T) -> bool {
+    serde_urlencoded::to_string(req).map_or(true, |form| !form.is_empty())
+}
+
+/// The export request for one format of [`UrlShortenerClient::export_all`].
+fn export_request(
+    short_code: &str,
+    password: Option<&str>,
+    format: &ExportFormat,
+) -> ExportRequest {
+    let req = ExportRequest::new(short_code, format.clone());
+    match password {
+        Some(password) => req.password(password),
+        None => req,
+    }
+}
+
+/// The stats request for a freshly shortened URL, with the password it was created with.
+fn stats_request_for(
+    shortened: &ShortenResponse,
+    password: Option<String>,
+) -> Result<StatsRequest, UrlShortenerError> {
+    let short_code = shortened.short_code().ok_or_else(|| {
+        UrlShortenerError::Other(format!("No short code in {}", shortened.short_url))
+    })?;
+    let req = StatsRequest::new(&short_code);
+    Ok(match password {
+        Some(password) => req.password(password),
+        None => req,
+    })
+}
+
+/// Read the `Content-Type` header, if it is valid text.
+fn content_type(headers: &HeaderMap) -> Option<String> {
+    headers.get(CONTENT_TYPE)?.to_str().ok().map(str::to_string)
+}
+
+/// Deserialize the JSON body of a successful response.
+fn deserialize_success<T: DeserializeOwned>(text: &str) -> Result<T, UrlShortenerError> {
+    Ok(serde_json::from_str(text)?)
 }
 
 impl Default for UrlShortenerClient {
@@ -616,3 +2659,116 @@ impl Default for UrlShortenerClient {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let headers = HeaderMap::new();
+        let api_error = |status: StatusCode, body: &str| match parse_error(status, &headers, body) {
+            UrlShortenerError::Api(err) => err,
+            other => panic!("Expected an API error, got: {:?}", other),
+        };
+
+        assert!(matches!(
+            api_error(StatusCode::TOO_MANY_REQUESTS, ""),
+            ApiError::RateLimitExceeded { retry_after: None }
+        ));
+        assert!(matches!(
+            api_error(StatusCode::BAD_REQUEST, r#"{"error": "UrlError"}"#),
+            ApiError::UrlError
+        ));
+        assert!(matches!(
+            api_error(StatusCode::BAD_REQUEST, r#"{"error": "AliasError"}"#),
+            ApiError::AliasError
+        ));
+        assert!(matches!(
+            api_error(StatusCode::UNAUTHORIZED, r#"{"error": "PasswordError"}"#),
+            ApiError::PasswordError
+        ));
+        assert!(matches!(
+            api_error(StatusCode::BAD_REQUEST, r#"{"error": "MaxClicksError"}"#),
+            ApiError::MaxClicksError
+        ));
+        assert!(matches!(
+            api_error(StatusCode::BAD_REQUEST, r#"{"error": "EmojiError"}"#),
+            ApiError::EmojiError
+        ));
+        assert!(matches!(
+            api_error(StatusCode::SERVICE_UNAVAILABLE, r#"{"error": "db busy"}"#),
+            ApiError::Other(ref msg) if msg == "db busy"
+        ));
+        assert!(matches!(
+            parse_error(StatusCode::INTERNAL_SERVER_ERROR, &headers, "oops"),
+            UrlShortenerError::Unexpected { status: 500, ref body } if body == "oops"
+        ));
+        assert!(matches!(
+            parse_error(StatusCode::FORBIDDEN, &headers, r#"{"message": "denied"}"#),
+            UrlShortenerError::Unexpected { status: 403, .. }
+        ));
+        assert!(matches!(
+            parse_error(StatusCode::BAD_GATEWAY, &headers, ""),
+            UrlShortenerError::Unexpected { status: 502, ref body } if body.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("12"));
+
+        assert!(matches!(
+            parse_error(StatusCode::TOO_MANY_REQUESTS, &headers, ""),
+            UrlShortenerError::Api(ApiError::RateLimitExceeded { retry_after: Some(d) })
+                if d == Duration::from_secs(12)
+        ));
+    }
+
+    #[test]
+    fn test_retry_after_http_date() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:30 GMT"),
+        );
+        headers.insert(
+            DATE,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(30)));
+
+        headers.insert(
+            DATE,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:29:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        headers.remove(DATE);
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_retry_after_missing_or_invalid() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_deserialize_success() {
+        let response: ShortenResponse = deserialize_success(
+            r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#,
+        )
+        .unwrap();
+        assert_eq!(response.short_url, "https://spoo.me/abc");
+
+        assert!(matches!(
+            deserialize_success::<ShortenResponse>("not json"),
+            Err(UrlShortenerError::Json(_))
+        ));
+    }
+}
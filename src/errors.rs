@@ -1,18 +1,29 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
 use thiserror::Error;
 
 /// Errors that can occur when sending requests (client validation or HTTP errors).
+///
+/// New variants may be added in minor releases, so matches need a `_ =>` arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ValidationError {
     /// Password does not meet format requirements.
-    InvalidPasswordFormat(String),
+    InvalidPasswordFormat(PasswordViolation),
     /// Alias does not meet format requirements
     InvalidAliasFormat(String),
     /// URL does not meet format requirements.
     InvalidUrlFormat(String),
     /// Max-clicks must be a positive integer.
     InvalidMaxClicks(u32),
+    /// Max-clicks is above the limit set with
+    /// [`with_max_clicks_limit`](crate::client::UrlShortenerClient::with_max_clicks_limit).
+    MaxClicksAboveLimit {
+        /// The max-clicks value of the request.
+        max_clicks: u32,
+        /// The client's limit.
+        limit: u32,
+    },
     /// Emoji sequence is invalid
     InvalidEmojiSequence(String),
 }
@@ -20,14 +31,21 @@ pub enum ValidationError {
 impl Display for ValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ValidationError::InvalidPasswordFormat(msg) => {
-                write!(f, "Invalid password format: {}", msg)
+            ValidationError::InvalidPasswordFormat(violation) => {
+                write!(f, "Invalid password format: {}", violation)
             }
             ValidationError::InvalidAliasFormat(msg) => write!(f, "Invalid alias format: {}", msg),
             ValidationError::InvalidUrlFormat(msg) => write!(f, "Invalid URL format: {}", msg),
             ValidationError::InvalidMaxClicks(value) => {
                 write!(f, "Max-clicks must be a positive integer, got: {}", value)
             }
+            ValidationError::MaxClicksAboveLimit { max_clicks, limit } => {
+                write!(
+                    f,
+                    "Max-clicks must be at most {}, got: {}",
+                    limit, max_clicks
+                )
+            }
             ValidationError::InvalidEmojiSequence(seq) => {
                 write!(f, "Invalid emoji sequence: {}", seq)
             }
@@ -35,8 +53,40 @@ impl Display for ValidationError {
     }
 }
 
+/// The first requirement a password fails to meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordViolation {
+    /// Shorter than 8 characters.
+    TooShort,
+    /// Contains no letter.
+    MissingLetter,
+    /// Contains no digit.
+    MissingDigit,
+    /// Contains neither '@' nor '.'.
+    MissingSpecial,
+    /// Contains two special characters ('@' or '.') in a row.
+    ConsecutiveSpecial,
+}
+
+impl Display for PasswordViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasswordViolation::TooShort => write!(f, "must be at least 8 characters long"),
+            PasswordViolation::MissingLetter => write!(f, "must contain a letter"),
+            PasswordViolation::MissingDigit => write!(f, "must contain a digit"),
+            PasswordViolation::MissingSpecial => write!(f, "must contain '@' or '.'"),
+            PasswordViolation::ConsecutiveSpecial => {
+                write!(f, "must not contain consecutive special characters")
+            }
+        }
+    }
+}
+
 /// Errors that can occur when interacting with the spoo.me API.
+///
+/// New variants may be added in minor releases, so matches need a `_ =>` arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ApiError {
     /// The URL does not match the expected format.
     UrlError,
@@ -49,7 +99,10 @@ pub enum ApiError {
     /// The emoji sequence is already in use or invalid.
     EmojiError,
     /// The rate limit for the API has been exceeded.
-    RateLimitExceeded,
+    RateLimitExceeded {
+        /// How long to wait before retrying, if the server said so with a `Retry-After` header.
+        retry_after: Option<Duration>,
+    },
     /// Other unexpected errors from the API.
     Other(String),
 }
@@ -62,24 +115,69 @@ impl Display for ApiError {
             ApiError::PasswordError => write!(f, "Incorrect password provided"),
             ApiError::MaxClicksError => write!(f, "Invalid max clicks value"),
             ApiError::EmojiError => write!(f, "Invalid or already used emoji sequence"),
-            ApiError::RateLimitExceeded => write!(f, "Rate limit exceeded for the API"),
-            ApiError::Other(msg) => write!(f, "API error: {}", msg),
+            ApiError::RateLimitExceeded { retry_after: None } => {
+                write!(f, "Rate limit exceeded for the API")
+            }
+            ApiError::RateLimitExceeded {
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "Rate limit exceeded for the API, retry after {}s",
+                retry_after.as_secs()
+            ),
+            ApiError::Other(msg) => write!(f, "{}", msg),
         }
     }
 }
 
 /// Errors that can occur when using the URL shortener client.
+///
+/// New variants may be added in minor releases, so matches need a `_ =>` arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum UrlShortenerError {
     /// Validation errors related to the request parameters.
-    Validation(ValidationError),
+    Validation(#[from] ValidationError),
     /// Errors returned by the spoo.me API.
-    Api(ApiError),
+    Api(#[from] ApiError),
     /// Errors related to the HTTP request, such as connection issues or timeouts.
-    Http(reqwest::Error),
+    Http(#[from] reqwest::Error),
     /// Errors related to JSON serialization or deserialization.
-    Json(serde_json::Error),
-    /// Other unexpected status codes or errors.
+    Json(#[from] serde_json::Error),
+    /// Errors writing data, such as an export, to a file or writer.
+    Io(#[from] std::io::Error),
+    /// Errors related to XML deserialization.
+    #[cfg(feature = "xml")]
+    Xml(#[from] quick_xml::DeError),
+    /// Errors encoding a QR code, such as a URL too long to fit in one.
+    #[cfg(feature = "qr")]
+    Qr(#[from] qrcode::types::QrError),
+    /// Errors encoding the image of a QR code as a PNG.
+    #[cfg(feature = "qr")]
+    QrImage(#[from] image::ImageError),
+    /// Errors reading the zip archive of a CSV export.
+    #[cfg(feature = "export-parse")]
+    Zip(#[from] zip::result::ZipError),
+    /// Errors parsing the CSV files of a CSV export.
+    #[cfg(feature = "export-parse")]
+    Csv(#[from] csv::Error),
+    /// Errors raised by a middleware of a `reqwest_middleware` client.
+    ///
+    /// Errors of the underlying request itself are reported as [`UrlShortenerError::Http`].
+    #[cfg(feature = "middleware")]
+    Middleware(reqwest_middleware::Error),
+    /// The request was aborted because its `CancellationToken` was cancelled.
+    #[cfg(feature = "cancellation")]
+    Cancelled,
+    /// An unsuccessful response whose body isn't a known API error, such as an HTML error page.
+    Unexpected {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The start of the response body, cut to
+        /// [`ERROR_BODY_SNIPPET_LEN`](crate::client::ERROR_BODY_SNIPPET_LEN) characters.
+        body: String,
+    },
+    /// Other unexpected errors.
     Other(String),
 }
 
@@ -87,10 +185,83 @@ impl Display for UrlShortenerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             UrlShortenerError::Validation(err) => write!(f, "Validation error: {}", err),
-            UrlShortenerError::Api(err) => write!(f, "API error: {:?}", err),
+            UrlShortenerError::Api(err) => write!(f, "API error: {}", err),
             UrlShortenerError::Http(err) => write!(f, "HTTP error: {}", err),
             UrlShortenerError::Json(err) => write!(f, "JSON error: {}", err),
+            UrlShortenerError::Io(err) => write!(f, "I/O error: {}", err),
+            #[cfg(feature = "xml")]
+            UrlShortenerError::Xml(err) => write!(f, "XML error: {}", err),
+            #[cfg(feature = "qr")]
+            UrlShortenerError::Qr(err) => write!(f, "QR code error: {}", err),
+            #[cfg(feature = "qr")]
+            UrlShortenerError::QrImage(err) => write!(f, "QR image error: {}", err),
+            #[cfg(feature = "export-parse")]
+            UrlShortenerError::Zip(err) => write!(f, "Zip error: {}", err),
+            #[cfg(feature = "export-parse")]
+            UrlShortenerError::Csv(err) => write!(f, "CSV error: {}", err),
+            #[cfg(feature = "middleware")]
+            UrlShortenerError::Middleware(err) => write!(f, "Middleware error: {}", err),
+            #[cfg(feature = "cancellation")]
+            UrlShortenerError::Cancelled => write!(f, "Request cancelled"),
+            UrlShortenerError::Unexpected { status, body } => {
+                write!(f, "Unexpected response with status {}: {}", status, body)
+            }
             UrlShortenerError::Other(msg) => write!(f, "Other error: {}", msg),
         }
     }
 }
+
+impl UrlShortenerError {
+    /// Whether this is an HTTP error caused by a request timing out.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, UrlShortenerError::Http(err) if err.is_timeout())
+    }
+
+    /// Whether this is an HTTP error caused by failing to connect, such as a DNS lookup failure or
+    /// a refused connection.
+    ///
+    /// Always `false` on wasm32 targets, where the browser doesn't report why a request failed.
+    pub fn is_connect(&self) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        return matches!(self, UrlShortenerError::Http(err) if err.is_connect());
+        #[cfg(target_arch = "wasm32")]
+        false
+    }
+
+    /// Whether this is an HTTP error that may go away when retrying, as it timed out or failed to
+    /// connect. See [`is_timeout`](Self::is_timeout) and [`is_connect`](Self::is_connect).
+    pub fn is_transient(&self) -> bool {
+        self.is_timeout() || self.is_connect()
+    }
+
+    /// Whether this is an HTTP error caused by failing to decode the response body.
+    ///
+    /// Bodies that were read but aren't valid JSON are [`UrlShortenerError::Json`] errors instead.
+    pub fn is_decode(&self) -> bool {
+        matches!(self, UrlShortenerError::Http(err) if err.is_decode())
+    }
+}
+
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::Error> for UrlShortenerError {
+    fn from(err: reqwest_middleware::Error) -> Self {
+        match err {
+            reqwest_middleware::Error::Reqwest(err) => UrlShortenerError::Http(err),
+            err => UrlShortenerError::Middleware(err),
+        }
+    }
+}
+
+/// Error parsing an [`ExportFormat`](crate::requests::ExportFormat) from a string.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct ParseExportFormatError(pub(crate) String);
+
+impl Display for ParseExportFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unknown export format '{}', expected json, csv, xlsx or xml",
+            self.0
+        )
+    }
+}
@@ -7,18 +7,71 @@
 //! # Features
 //! - `blocking`: Enables blocking methods for the client, allowing synchronous calls to the API.
 //! - `custom_url`: Allows setting a custom base URL for the client, useful for self-hosted instances of spoo.me.
+//! - `chrono`: Adds date-aware helpers for working with stats responses.
+//! - `xml`: Allows parsing XML exports into stats responses.
+//! - `url`: Adds optional normalization of target URLs before they are shortened.
+//! - `isocountry`: Parses the country keys of stats responses into ISO 3166-1 country codes.
+//! - `tracing`: Traces each API call as a span with its endpoint, short code, status and duration.
+//! - `metrics`: Counts each API call in `spoo_me_requests_total` and records its duration in
+//!   `spoo_me_request_duration_seconds`, through the `metrics` crate's facade. Both are labelled
+//!   with the `endpoint` and `outcome` of the call.
+//! - `qr`: Renders QR codes of short URLs locally, as PNG or SVG images.
+//! - `export-parse`: Parses CSV exports, unzipping them into the records of each CSV file.
+//! - `middleware`: Sends async requests through a `reqwest_middleware::ClientWithMiddleware`, so
+//!   middleware such as retries or logging can be registered.
+//! - `cancellation`: Adds async request variants taking a `tokio_util::sync::CancellationToken`,
+//!   which abort the request when the token is cancelled.
+//!
+//! ## TLS backends
+//! - `rustls-tls` (default): Uses rustls, which needs no system libraries and works on musl
+//!   targets such as Alpine.
+//! - `native-tls`: Uses the platform's TLS library (OpenSSL on Linux). Disable default features
+//!   to use it on its own.
+//!
+//! ## WebAssembly
+//! The async client builds for `wasm32-unknown-unknown`, sending requests with the browser's
+//! `fetch` and waiting between retries on the browser's timers. The TLS features have no effect
+//! there, as TLS is left to the browser:
+//!
+//! ```text
+//! cargo check --target wasm32-unknown-unknown --features full
+//! ```
+//!
+//! The `blocking` feature doesn't build for wasm32. Resolving, following and verifying redirects
+//! isn't available, as the browser always follows redirects itself, and neither are the
+//! client-wide timeout and connection settings; per-request timeouts still work.
+//!
+//! ## Compression
+//! - `gzip`: Asks for gzip-compressed responses, such as large exports, and decompresses them.
+//! - `brotli`: Asks for brotli-compressed responses and decompresses them.
+//!
+//! Decompression can be turned off with
+//! [`UrlShortenerClientBuilder::decompression`](client::UrlShortenerClientBuilder::decompression).
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+#[cfg(all(feature = "blocking", target_arch = "wasm32"))]
+compile_error!("the `blocking` feature is not supported on wasm32 targets, use the async client");
+
 /// A client for the URL shortener API.
 pub mod client;
 
 /// Errors related to a request.
 pub mod errors;
 
+/// Re-exports of the client, its requests and responses, and its error type, for
+/// `use spoo_me::prelude::*`.
+pub mod prelude;
+
 /// Requests and responses for the URL shortener API.
 pub mod requests;
 
+/// Retry policies for requests that fail with transient errors.
+pub mod retry;
+
+/// Traits over the client's endpoints, for swapping in a fake in tests.
+pub mod shortener;
+
 /// Tools for validating and formatting requests.
 pub mod utils;
@@ -0,0 +1,14 @@
+pub use crate::{
+    client::UrlShortenerClient,
+    errors::UrlShortenerError,
+    requests::{
+        EmojiRequest, EmojiResponse, ExportFormat, ExportRequest, ExportResponse, FollowResult,
+        ResolveRequest, ResolveResponse, ShortResult, ShortenOptions, ShortenRequest,
+        ShortenResponse, StatsRequest, StatsResponse,
+    },
+};
+
+#[cfg(feature = "custom_url")]
+pub use crate::requests::DeleteRequest;
+#[cfg(feature = "qr")]
+pub use crate::requests::{QrFormat, QrOptions, QrResponse};
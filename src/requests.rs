@@ -1,19 +1,133 @@
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt::Display};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde::{
+    de::{self, DeserializeOwned, Deserializer, Visitor},
+    Deserialize, Serialize,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    fmt::Display,
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{
+    errors::{ParseExportFormatError, UrlShortenerError, ValidationError},
+    utils::{
+        is_reserved_alias, is_valid_alias, is_valid_emoji, is_valid_emoji_count,
+        is_valid_max_clicks, is_valid_short_code, is_valid_url, is_valid_url_allowing_ftp,
+        percent_encode_url, safe_file_stem, safe_filename, validate_password, MAX_EMOJI_COUNT,
+        MIN_EMOJI_COUNT,
+    },
+};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 /// Response for URL-shortening endpoints (`/` and `/emoji`).
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ShortenResponse {
     /// The resulting shortened URL (full URL).
     pub short_url: String,
-    /// The domain name used for the short URL - currently always "spoo.me".
+    /// The domain name used for the short URL, such as "spoo.me" or a self-hosted instance's domain.
     pub domain: String,
-    /// The URL that was shortened.
+    /// The URL that was shortened, after any percent-encoding or normalization by the client.
     pub original_url: String,
+    /// Whether the short URL is password protected, if reported by the API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_protected: Option<bool>,
+    /// The max-clicks limit applied to the short URL, if reported by the API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_clicks: Option<u32>,
+    /// Whether bots are blocked from the short URL, if reported by the API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_bots: Option<bool>,
+}
+
+impl ShortenResponse {
+    /// Whether the echoed `original_url` is the same URL as `input`, the URL that was sent.
+    ///
+    /// Both are compared as parsed URLs, so differences in scheme or host case, an explicit
+    /// default port, or a trailing slash don't count. Falls back to comparing the strings if
+    /// either doesn't parse.
+    pub fn matches_input(&self, input: &str) -> bool {
+        match (
+            reqwest::Url::parse(&self.original_url),
+            reqwest::Url::parse(input),
+        ) {
+            (Ok(original), Ok(input)) => comparable_url(&original) == comparable_url(&input),
+            _ => self.original_url == input,
+        }
+    }
+
+    /// The short code of `short_url`, such as `ga` for `https://spoo.me/ga`, to pass to
+    /// [`StatsRequest::new`] and friends. `None` if `short_url` isn't a URL with a path.
+    pub fn short_code(&self) -> Option<String> {
+        short_code_of(&self.short_url)
+    }
+
+    /// Whether `short_url` is on the host of `base_url`, such as the base URL of the client
+    /// (see [`UrlShortenerClient::base_url`](crate::client::UrlShortenerClient::base_url)).
+    ///
+    /// The hosts are compared ignoring case, along with the port if either URL has a
+    /// non-default one. `base_url` may leave out the scheme, as in `spoo.me`.
+    pub fn is_on(&self, base_url: &str) -> bool {
+        is_on_host(&self.short_url, base_url)
+    }
+}
+
+impl Display for ShortenResponse {
+    /// Shows the short URL.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.short_url)
+    }
+}
+
+/// The parts of a URL that decide whether two URLs are the same, ignoring a trailing slash.
+fn comparable_url(url: &reqwest::Url) -> impl PartialEq + '_ {
+    (
+        url.scheme(),
+        url.username(),
+        url.password(),
+        url.host_str(),
+        url.port_or_known_default(),
+        url.path().trim_end_matches('/'),
+        url.query(),
+        url.fragment(),
+    )
+}
+
+/// Options for shortening a URL, as a plain struct for loading from configuration files.
+///
+/// Every field is optional, and fields missing from the configuration are left unset. Apply them
+/// with [`ShortenRequest::with_options`]:
+///
+/// ```
+/// use spoo_me::requests::{ShortenOptions, ShortenRequest};
+///
+/// let options: ShortenOptions = serde_json::from_str(r#"{"max_clicks": 10, "block_bots": true}"#)?;
+/// let request = ShortenRequest::with_options("https://example.com", options);
+/// assert_eq!(request.get_max_clicks(), Some(10));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Deserialize, Default, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ShortenOptions {
+    /// Custom alias (must follow API rules).
+    pub alias: Option<String>,
+    /// Password (must follow API rules).
+    pub password: Option<String>,
+    /// Max-clicks (must be positive). Also read from `max-clicks`, as the API names it.
+    #[serde(alias = "max-clicks")]
+    pub max_clicks: Option<u32>,
+    /// Block bots flag. Also read from `block-bots`, as the API names it.
+    #[serde(alias = "block-bots")]
+    pub block_bots: Option<bool>,
 }
 
 /// Request payload for `POST /` (shorten URL).
-#[derive(Debug, Serialize, Default, Clone)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
 pub struct ShortenRequest {
     pub(crate) url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -24,6 +138,14 @@ pub struct ShortenRequest {
     pub(crate) max_clicks: Option<u32>,
     #[serde(rename = "block-bots", skip_serializing_if = "Option::is_none")]
     pub(crate) block_bots: Option<bool>,
+    #[serde(skip_serializing)]
+    pub(crate) correlation_id: Option<String>,
+    #[serde(skip_serializing)]
+    pub(crate) idempotency_key: Option<String>,
+    #[serde(skip)]
+    pub(crate) timeout: Option<Duration>,
+    #[serde(skip)]
+    pub(crate) allow_ftp: bool,
 }
 
 impl ShortenRequest {
@@ -34,6 +156,22 @@ impl ShortenRequest {
             ..Default::default()
         }
     }
+    /// Creates a new ShortenRequest with the mandatory `url` that blocks bots, as with
+    /// [`block_bots(true)`](Self::block_bots).
+    pub fn new_blocking_bots<U: Into<String>>(url: U) -> Self {
+        Self::new(url).block_bots(true)
+    }
+    /// Creates a new ShortenRequest with the mandatory `url` and the options set in `options`.
+    pub fn with_options<U: Into<String>>(url: U, options: ShortenOptions) -> Self {
+        ShortenRequest {
+            url: url.into(),
+            alias: options.alias,
+            password: options.password,
+            max_clicks: options.max_clicks,
+            block_bots: options.block_bots,
+            ..Default::default()
+        }
+    }
     /// Optional custom alias (must follow API rules)
     pub fn alias<A: Into<String>>(mut self, alias: A) -> Self {
         self.alias = Some(alias.into());
@@ -54,10 +192,155 @@ impl ShortenRequest {
         self.block_bots = Some(flag);
         self
     }
+    /// Optional correlation ID, sent as the `X-Correlation-Id` header with this request only.
+    ///
+    /// Ignored if it isn't a valid header value.
+    pub fn correlation_id<C: Into<String>>(mut self, id: C) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+    /// Optional idempotency key, sent as the `Idempotency-Key` header.
+    ///
+    /// The same key is sent on every retry of the request. spoo.me doesn't document support for
+    /// idempotency keys, so it may still create a duplicate short URL; the key is mainly for
+    /// self-hosted instances or proxies that honour it, and for deduplicating on the caller's
+    /// side. Ignored if it isn't a valid header value.
+    pub fn idempotency_key<K: Into<String>>(mut self, key: K) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+    /// Set a random UUID (v4) as the idempotency key, readable with
+    /// [`get_idempotency_key`](Self::get_idempotency_key).
+    ///
+    /// See [`idempotency_key`](Self::idempotency_key) for how the key is used.
+    pub fn generate_idempotency_key(self) -> Self {
+        self.idempotency_key(uuid::Uuid::new_v4().to_string())
+    }
+    /// Accept `ftp://` target URLs, for instances that shorten them.
+    ///
+    /// spoo.me only shortens web URLs, so FTP URLs are rejected with
+    /// [`InvalidUrlFormat`](ValidationError::InvalidUrlFormat) before sending by default.
+    pub fn allow_ftp(mut self, allow: bool) -> Self {
+        self.allow_ftp = allow;
+        self
+    }
+    /// Optional timeout for this request only, overriding the client's timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The URL to shorten.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    /// The custom alias, if set. Named `get_*` as [`alias`](Self::alias) sets it.
+    pub fn get_alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+    /// Whether a password is set. The password itself isn't exposed.
+    pub fn has_password(&self) -> bool {
+        self.password.is_some()
+    }
+    /// The max-clicks limit, if set.
+    pub fn get_max_clicks(&self) -> Option<u32> {
+        self.max_clicks
+    }
+    /// The block bots flag, if set.
+    pub fn get_block_bots(&self) -> Option<bool> {
+        self.block_bots
+    }
+    /// The idempotency key, if set.
+    pub fn get_idempotency_key(&self) -> Option<&str> {
+        self.idempotency_key.as_deref()
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    ///
+    /// With the `custom_url` feature, URLs pointing at `base_url` are rejected instead of ones
+    /// pointing at spoo.me.
+    #[cfg(feature = "custom_url")]
+    pub fn validate(&self, base_url: &str) -> Result<(), ValidationError> {
+        validate_password_opt(&self.password)?;
+        let valid = if self.allow_ftp {
+            is_valid_url_allowing_ftp(&self.url, base_url)
+        } else {
+            is_valid_url(&self.url, base_url)
+        };
+        if !valid {
+            return Err(ValidationError::InvalidUrlFormat(self.url.clone()));
+        }
+        self.validate_rest()
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    ///
+    /// With the `custom_url` feature, URLs pointing at `base_url` are rejected instead of ones
+    /// pointing at spoo.me.
+    #[cfg(not(feature = "custom_url"))]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_password_opt(&self.password)?;
+        let valid = if self.allow_ftp {
+            is_valid_url_allowing_ftp(&self.url)
+        } else {
+            is_valid_url(&self.url)
+        };
+        if !valid {
+            return Err(ValidationError::InvalidUrlFormat(self.url.clone()));
+        }
+        self.validate_rest()
+    }
+
+    /// Checks that don't depend on the base URL.
+    fn validate_rest(&self) -> Result<(), ValidationError> {
+        if let Some(ref alias) = self.alias {
+            if !is_valid_alias(alias) || is_reserved_alias(alias) {
+                return Err(ValidationError::InvalidAliasFormat(alias.clone()));
+            }
+        }
+        if let Some(max_clicks) = self.max_clicks {
+            if !is_valid_max_clicks(max_clicks) {
+                return Err(ValidationError::InvalidMaxClicks(max_clicks));
+            }
+        }
+        Ok(())
+    }
+
+    /// Headers specific to this request.
+    pub(crate) fn headers(&self) -> HeaderMap {
+        let mut headers = request_headers(&self.correlation_id);
+        if let Some(value) = self
+            .idempotency_key
+            .as_deref()
+            .and_then(|key| HeaderValue::from_str(key).ok())
+        {
+            headers.insert("Idempotency-Key", value);
+        }
+        headers
+    }
+}
+
+impl TryFrom<&str> for ShortenRequest {
+    type Error = ValidationError;
+
+    /// Create a ShortenRequest for `url`, checking the URL straight away rather than when the
+    /// request is sent.
+    ///
+    /// The URL is percent-encoded like the client does before sending it, then checked against
+    /// spoo.me. With the `custom_url` feature, the client checks it against its own base URL again
+    /// when sending it.
+    fn try_from(url: &str) -> Result<Self, Self::Error> {
+        let req = ShortenRequest::new(percent_encode_url(url));
+        #[cfg(feature = "custom_url")]
+        req.validate("spoo.me")?;
+        #[cfg(not(feature = "custom_url"))]
+        req.validate()?;
+        Ok(req)
+    }
 }
 
 /// Request payload for `POST /emoji` (uses emojis as slug).
-#[derive(Debug, Serialize, Default, Clone)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
 pub struct EmojiRequest {
     pub(crate) url: String,
     #[serde(rename = "emojies", skip_serializing_if = "Option::is_none")]
@@ -68,6 +351,14 @@ pub struct EmojiRequest {
     pub(crate) max_clicks: Option<u32>,
     #[serde(rename = "block-bots", skip_serializing_if = "Option::is_none")]
     pub(crate) block_bots: Option<bool>,
+    #[serde(skip_serializing)]
+    pub(crate) correlation_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) timeout: Option<Duration>,
+    #[serde(skip)]
+    pub(crate) emoji_count_limits: Option<(usize, usize)>,
+    #[serde(skip)]
+    pub(crate) allow_ftp: bool,
 }
 
 impl EmojiRequest {
@@ -83,6 +374,13 @@ impl EmojiRequest {
         self.emojies = Some(seq.into());
         self
     }
+    /// Optional bounds on how many emoji the sequence may contain, inclusive, overriding the
+    /// defaults of [`MIN_EMOJI_COUNT`] and [`MAX_EMOJI_COUNT`]. Useful for self-hosted
+    /// instances with a different limit.
+    pub fn emoji_count_limits(mut self, min: usize, max: usize) -> Self {
+        self.emoji_count_limits = Some((min, max));
+        self
+    }
     /// Optional password (must follow API rules).
     pub fn password<P: Into<String>>(mut self, password: P) -> Self {
         self.password = Some(password.into());
@@ -98,26 +396,275 @@ impl EmojiRequest {
         self.block_bots = Some(flag);
         self
     }
+    /// Optional correlation ID, sent as the `X-Correlation-Id` header with this request only.
+    ///
+    /// Ignored if it isn't a valid header value.
+    pub fn correlation_id<C: Into<String>>(mut self, id: C) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+    /// Accept `ftp://` target URLs, for instances that shorten them.
+    ///
+    /// spoo.me only shortens web URLs, so FTP URLs are rejected with
+    /// [`InvalidUrlFormat`](ValidationError::InvalidUrlFormat) before sending by default.
+    pub fn allow_ftp(mut self, allow: bool) -> Self {
+        self.allow_ftp = allow;
+        self
+    }
+    /// Optional timeout for this request only, overriding the client's timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The URL to shorten.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    /// The emoji sequence, if set. Named `get_*` as [`emojies`](Self::emojies) sets it.
+    pub fn get_emojies(&self) -> Option<&str> {
+        self.emojies.as_deref()
+    }
+    /// Whether a password is set. The password itself isn't exposed.
+    pub fn has_password(&self) -> bool {
+        self.password.is_some()
+    }
+    /// The max-clicks limit, if set.
+    pub fn get_max_clicks(&self) -> Option<u32> {
+        self.max_clicks
+    }
+    /// The block bots flag, if set.
+    pub fn get_block_bots(&self) -> Option<bool> {
+        self.block_bots
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    ///
+    /// With the `custom_url` feature, URLs pointing at `base_url` are rejected instead of ones
+    /// pointing at spoo.me.
+    #[cfg(feature = "custom_url")]
+    pub fn validate(&self, base_url: &str) -> Result<(), ValidationError> {
+        validate_password_opt(&self.password)?;
+        let valid = if self.allow_ftp {
+            is_valid_url_allowing_ftp(&self.url, base_url)
+        } else {
+            is_valid_url(&self.url, base_url)
+        };
+        if !valid {
+            return Err(ValidationError::InvalidUrlFormat(self.url.clone()));
+        }
+        self.validate_rest()
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    ///
+    /// With the `custom_url` feature, URLs pointing at `base_url` are rejected instead of ones
+    /// pointing at spoo.me.
+    #[cfg(not(feature = "custom_url"))]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_password_opt(&self.password)?;
+        let valid = if self.allow_ftp {
+            is_valid_url_allowing_ftp(&self.url)
+        } else {
+            is_valid_url(&self.url)
+        };
+        if !valid {
+            return Err(ValidationError::InvalidUrlFormat(self.url.clone()));
+        }
+        self.validate_rest()
+    }
+
+    /// Checks that don't depend on the base URL.
+    fn validate_rest(&self) -> Result<(), ValidationError> {
+        if let Some(max_clicks) = self.max_clicks {
+            if !is_valid_max_clicks(max_clicks) {
+                return Err(ValidationError::InvalidMaxClicks(max_clicks));
+            }
+        }
+        if let Some(ref emojies) = self.emojies {
+            let (min, max) = self
+                .emoji_count_limits
+                .unwrap_or((MIN_EMOJI_COUNT, MAX_EMOJI_COUNT));
+            if !is_valid_emoji(emojies) || !is_valid_emoji_count(emojies, min, max) {
+                return Err(ValidationError::InvalidEmojiSequence(emojies.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Headers specific to this request.
+    pub(crate) fn headers(&self) -> HeaderMap {
+        request_headers(&self.correlation_id)
+    }
+}
+
+/// Validate an optional password, as sent by the shorten, emoji and stats requests.
+fn validate_password_opt(password: &Option<String>) -> Result<(), ValidationError> {
+    match password {
+        Some(pw) => validate_password(pw).map_err(ValidationError::InvalidPasswordFormat),
+        None => Ok(()),
+    }
+}
+
+/// Build the per-request headers shared by all request types.
+fn request_headers(correlation_id: &Option<String>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(value) = correlation_id
+        .as_deref()
+        .and_then(|id| HeaderValue::from_str(id).ok())
+    {
+        headers.insert("X-Correlation-Id", value);
+    }
+    headers
 }
 
 /// Response struct for `POST /emoji`, containing the shortened URL.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct EmojiResponse {
     /// The resulting shortened URL (full URL).
     pub short_url: String,
-    /// The domain name used for the short URL - currently always "spoo.me".
+    /// The domain name used for the short URL, such as "spoo.me" or a self-hosted instance's domain.
     pub domain: String,
-    /// The URL that was shortened.
+    /// The URL that was shortened, after any percent-encoding or normalization by the client.
     pub original_url: String,
 }
 
+impl EmojiResponse {
+    /// The emoji short code of `short_url`, such as `🍕🔥` for `https://spoo.me/🍕🔥`. See
+    /// [`ShortenResponse::short_code`].
+    pub fn short_code(&self) -> Option<String> {
+        short_code_of(&self.short_url)
+    }
+
+    /// Whether `short_url` is on the host of `base_url`. See [`ShortenResponse::is_on`].
+    pub fn is_on(&self, base_url: &str) -> bool {
+        is_on_host(&self.short_url, base_url)
+    }
+}
+
+impl Display for EmojiResponse {
+    /// Shows the short URL.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.short_url)
+    }
+}
+
+/// The fields shared by [`ShortenResponse`] and [`EmojiResponse`], for code that handles either.
+///
+/// ```rust
+/// use spoo_me::requests::ShortResult;
+///
+/// fn describe(result: &impl ShortResult) -> String {
+///     format!("{} -> {}", result.short_url(), result.original_url())
+/// }
+/// ```
+pub trait ShortResult {
+    /// The resulting shortened URL (full URL).
+    fn short_url(&self) -> &str;
+
+    /// The domain name used for the short URL.
+    fn domain(&self) -> &str;
+
+    /// The URL that was shortened, after any percent-encoding or normalization by the client.
+    fn original_url(&self) -> &str;
+
+    /// The short code of the short URL. See [`ShortenResponse::short_code`].
+    fn short_code(&self) -> Option<String> {
+        short_code_of(self.short_url())
+    }
+
+    /// Whether the short URL is on the host of `base_url`. See [`ShortenResponse::is_on`].
+    fn is_on(&self, base_url: &str) -> bool {
+        is_on_host(self.short_url(), base_url)
+    }
+}
+
+impl ShortResult for ShortenResponse {
+    fn short_url(&self) -> &str {
+        &self.short_url
+    }
+
+    fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    fn original_url(&self) -> &str {
+        &self.original_url
+    }
+}
+
+impl ShortResult for EmojiResponse {
+    fn short_url(&self) -> &str {
+        &self.short_url
+    }
+
+    fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    fn original_url(&self) -> &str {
+        &self.original_url
+    }
+}
+
+/// Whether `short_url` has the same host and port as `base_url`, which defaults to `https`.
+pub(crate) fn is_on_host(short_url: &str, base_url: &str) -> bool {
+    let base = reqwest::Url::parse(base_url)
+        .ok()
+        .filter(|url| url.has_host())
+        .or_else(|| reqwest::Url::parse(&format!("https://{}", base_url)).ok());
+    match (reqwest::Url::parse(short_url), base) {
+        (Ok(short), Some(base)) => {
+            short.host_str().is_some()
+                && short.host_str() == base.host_str()
+                && short.port_or_known_default() == base.port_or_known_default()
+        }
+        _ => false,
+    }
+}
+
+/// The last path segment of a short URL, percent-decoded.
+fn short_code_of(short_url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(short_url).ok()?;
+    let segment = url.path_segments()?.rfind(|segment| !segment.is_empty())?;
+    percent_decode(segment)
+}
+
+/// Decode `%XX` escapes, as in the path of a parsed URL. `None` if the result isn't UTF-8.
+fn percent_decode(encoded: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut rest = encoded.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .filter(|hex| byte == b'%' && hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
 /// Request payload for `POST /stats/{shortCode}`.
-#[derive(Debug, Serialize, Default, Clone)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
 pub struct StatsRequest {
     #[serde(skip_serializing)]
     pub(crate) short_code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) password: Option<String>,
+    #[serde(skip_serializing)]
+    pub(crate) correlation_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) timeout: Option<Duration>,
 }
 
 impl StatsRequest {
@@ -126,26 +673,60 @@ impl StatsRequest {
         StatsRequest {
             short_code: short_code.to_string(),
             password: None,
+            correlation_id: None,
+            timeout: None,
         }
     }
+    /// Create a StatsRequest for a password-protected short URL.
+    ///
+    /// Shorthand for `StatsRequest::new(short_code).password(password)`.
+    pub fn with_password<P: Into<String>>(short_code: &str, password: P) -> Self {
+        Self::new(short_code).password(password)
+    }
     /// Optional password for accessing stats (if set on the short URL).
     pub fn password<P: Into<String>>(mut self, password: P) -> Self {
         self.password = Some(password.into());
         self
     }
+    /// Optional correlation ID, sent as the `X-Correlation-Id` header with this request only.
+    ///
+    /// Ignored if it isn't a valid header value.
+    pub fn correlation_id<C: Into<String>>(mut self, id: C) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+    /// Optional timeout for this request only, overriding the client's timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if !is_valid_short_code(&self.short_code) {
+            return Err(ValidationError::InvalidAliasFormat(self.short_code.clone()));
+        }
+        validate_password_opt(&self.password)
+    }
+
+    /// Headers specific to this request.
+    pub(crate) fn headers(&self) -> HeaderMap {
+        request_headers(&self.correlation_id)
+    }
 }
 
 /// Response struct for `POST /stats/{shortCode}`, containing URL statistics.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct StatsResponse {
     /// The code of the short URL.
     pub short_code: String,
     /// The original long URL.
     pub url: String,
     /// Total clicks since creation.
-    #[serde(rename = "total-clicks")]
+    #[serde(rename = "total-clicks", deserialize_with = "lenient_u32")]
     pub total_clicks: u32,
     /// Total unique clicks.
+    #[serde(deserialize_with = "lenient_u32")]
     pub total_unique_clicks: u32,
     /// Creation date (string) of the short link, if available.
     #[serde(rename = "creation-date")]
@@ -162,7 +743,7 @@ pub struct StatsResponse {
     #[serde(rename = "last-click-os")]
     pub last_click_os: Option<String>,
     /// Max clicks allowed (if set).
-    #[serde(rename = "max-clicks")]
+    #[serde(rename = "max-clicks", default, deserialize_with = "lenient_u32_opt")]
     pub max_clicks: Option<u32>,
     /// The password set on the short URL (if any).
     pub password: Option<String>,
@@ -186,10 +767,534 @@ pub struct StatsResponse {
     pub unique_os_name: Option<HashMap<String, u32>>,
     /// Unique clicks per referrer.
     pub unique_referrer: Option<HashMap<String, u32>>,
+    /// Fields returned by the API that this struct doesn't model yet, such as new stats
+    /// breakdowns, keyed by their name in the response.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl StatsResponse {
+    /// The `n` countries with the most clicks, most clicked first.
+    pub fn top_countries(&self, n: usize) -> Vec<(String, u32)> {
+        top_entries(&self.country, n)
+    }
+
+    /// The `n` browsers with the most clicks, most clicked first.
+    pub fn top_browsers(&self, n: usize) -> Vec<(String, u32)> {
+        top_entries(&self.browser, n)
+    }
+
+    /// Clicks on `date` (formatted `YYYY-MM-DD`, as in `counter`), or `0` if there were none.
+    pub fn clicks_on(&self, date: &str) -> u32 {
+        self.counter
+            .as_ref()
+            .and_then(|counter| counter.get(date))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Clicks per day from `counter`, in chronological order. Empty if there is no click data.
+    ///
+    /// `YYYY-MM-DD` keys sort the same as strings, so this needs no date parsing; see
+    /// [`counter_by_date`](Self::counter_by_date) for parsed keys.
+    pub fn counter_sorted(&self) -> BTreeMap<String, u32> {
+        self.counter
+            .iter()
+            .flatten()
+            .map(|(day, clicks)| (day.clone(), *clicks))
+            .collect()
+    }
+
+    /// The days of `counter` from `from` to `to`, both inclusive and formatted `YYYY-MM-DD`.
+    ///
+    /// The stats route takes no date-range parameters, so this filters the days client-side. Days
+    /// without clicks aren't in `counter`, so they aren't in the result either. Keys that aren't
+    /// `YYYY-MM-DD` dates are left out.
+    pub fn counter_between(&self, from: &str, to: &str) -> HashMap<String, u32> {
+        days_between(&self.counter, from, to)
+    }
+
+    /// The days of `unique_counter` from `from` to `to`, like [`counter_between`](Self::counter_between).
+    pub fn unique_counter_between(&self, from: &str, to: &str) -> HashMap<String, u32> {
+        days_between(&self.unique_counter, from, to)
+    }
+
+    /// Sum of the clicks per day in `counter`, for cross-checking against `total_clicks`.
+    pub fn total_from_counter(&self) -> u32 {
+        self.counter
+            .iter()
+            .flatten()
+            .map(|(_, clicks)| clicks)
+            .sum()
+    }
+
+    /// Clicks left before the short URL reaches its max-clicks limit, or `None` if it has none.
+    ///
+    /// The limit counts every click, not just unique ones, so this is based on `total_clicks`.
+    pub fn clicks_remaining(&self) -> Option<u32> {
+        self.max_clicks
+            .map(|max_clicks| max_clicks.saturating_sub(self.total_clicks))
+    }
+
+    /// Clicks per kind of bot, parsed from the keys of `bots`.
+    ///
+    /// Keys of the same kind, such as `Googlebot` and `Bingbot`, are merged.
+    pub fn bots_typed(&self) -> HashMap<BotType, u32> {
+        let mut bots = HashMap::new();
+        for (key, clicks) in self.bots.iter().flatten() {
+            *bots.entry(BotType::parse(key)).or_insert(0) += clicks;
+        }
+        bots
+    }
+
+    /// Whether the short URL has reached its max-clicks limit.
+    pub fn is_exhausted(&self) -> bool {
+        self.clicks_remaining() == Some(0)
+    }
+}
+
+impl Display for StatsResponse {
+    /// Shows a one-line summary, such as `ga: 12 clicks (7 unique)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} clicks ({} unique)",
+            self.short_code, self.total_clicks, self.total_unique_clicks
+        )
+    }
+}
+
+/// A `u32` that some self-hosted instances send as a string, such as `"42"` instead of `42`.
+struct LenientU32(u32);
+
+impl<'de> Deserialize<'de> for LenientU32 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LenientU32Visitor;
+
+        impl<'de> Visitor<'de> for LenientU32Visitor {
+            type Value = LenientU32;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a non-negative integer, or a string holding one")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<LenientU32, E> {
+                u32::try_from(value)
+                    .map(LenientU32)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<LenientU32, E> {
+                u32::try_from(value)
+                    .map(LenientU32)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<LenientU32, E> {
+                value
+                    .trim()
+                    .parse()
+                    .map(LenientU32)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+
+            // XML exports buffered for `StatsResponse::extra` hand over an element's text as
+            // a map with a single `$text` entry.
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<LenientU32, A::Error> {
+                match map.next_key::<String>()?.as_deref() {
+                    Some("$text" | "$value") => {
+                        let value = map.next_value()?;
+                        match map.next_key::<String>()? {
+                            None => Ok(value),
+                            Some(_) => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+                        }
+                    }
+                    _ => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(LenientU32Visitor)
+    }
+}
+
+/// Deserialize a `u32` sent either as a number or as a string.
+fn lenient_u32<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    LenientU32::deserialize(deserializer).map(|LenientU32(value)| value)
+}
+
+/// Deserialize an optional `u32` sent either as a number or as a string.
+fn lenient_u32_opt<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+    Ok(Option::<LenientU32>::deserialize(deserializer)?.map(|LenientU32(value)| value))
+}
+
+/// A kind of bot, as parsed from a key of the stats `bots` map.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BotType {
+    /// A search engine crawler, such as `Googlebot`.
+    Search,
+    /// A link preview fetcher of a social network or chat app, such as `Twitterbot`.
+    Social,
+    /// An uptime or performance monitor, such as `UptimeRobot`.
+    Monitoring,
+    /// A bot that isn't one of the known kinds.
+    Unknown(String),
+}
+
+/// Name fragments of search engine crawlers.
+const SEARCH_BOTS: &[&str] = &[
+    "googlebot",
+    "bingbot",
+    "yandex",
+    "baiduspider",
+    "duckduckbot",
+    "slurp",
+    "applebot",
+    "petalbot",
+    "sogou",
+];
+
+/// Name fragments of social network and chat app link preview fetchers.
+const SOCIAL_BOTS: &[&str] = &[
+    "facebookexternalhit",
+    "facebot",
+    "twitterbot",
+    "linkedinbot",
+    "slackbot",
+    "discordbot",
+    "telegrambot",
+    "whatsapp",
+    "pinterest",
+    "redditbot",
+    "skypeuripreview",
+];
+
+/// Name fragments of uptime and performance monitors.
+const MONITORING_BOTS: &[&str] = &[
+    "uptimerobot",
+    "pingdom",
+    "statuscake",
+    "site24x7",
+    "betteruptime",
+    "newrelicpinger",
+    "datadog",
+];
+
+impl BotType {
+    /// Classify a bot name by the known names it contains, ignoring case. Names that aren't
+    /// recognized become [`BotType::Unknown`].
+    pub fn parse(key: &str) -> BotType {
+        let name = key.trim().to_ascii_lowercase();
+        let matches = |bots: &[&str]| bots.iter().any(|bot| name.contains(bot));
+        if matches(SEARCH_BOTS) {
+            BotType::Search
+        } else if matches(SOCIAL_BOTS) {
+            BotType::Social
+        } else if matches(MONITORING_BOTS) {
+            BotType::Monitoring
+        } else {
+            BotType::Unknown(key.trim().to_string())
+        }
+    }
+}
+
+impl FromStr for BotType {
+    type Err = Infallible;
+
+    /// Same as [`BotType::parse`], which never fails.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(BotType::parse(s))
+    }
+}
+
+/// The entries of a per-day click map from `from` to `to`, both inclusive.
+///
+/// `YYYY-MM-DD` dates sort the same as strings, so no parsing is needed.
+fn days_between(
+    counter: &Option<HashMap<String, u32>>,
+    from: &str,
+    to: &str,
+) -> HashMap<String, u32> {
+    counter
+        .iter()
+        .flatten()
+        .filter(|(day, _)| is_date_key(day) && from <= day.as_str() && day.as_str() <= to)
+        .map(|(day, clicks)| (day.clone(), *clicks))
+        .collect()
+}
+
+/// Whether a key has the `YYYY-MM-DD` shape of a day.
+fn is_date_key(key: &str) -> bool {
+    key.len() == 10
+        && key.char_indices().all(|(i, c)| match i {
+            4 | 7 => c == '-',
+            _ => c.is_ascii_digit(),
+        })
+}
+
+/// The `n` entries of a click map with the most clicks, ties broken by name.
+fn top_entries(map: &Option<HashMap<String, u32>>, n: usize) -> Vec<(String, u32)> {
+    let mut entries: Vec<_> = map
+        .iter()
+        .flatten()
+        .map(|(name, clicks)| (name.clone(), *clicks))
+        .collect();
+    entries.sort_by(|(a_name, a_clicks), (b_name, b_clicks)| {
+        b_clicks.cmp(a_clicks).then_with(|| a_name.cmp(b_name))
+    });
+    entries.truncate(n);
+    entries
+}
+
+/// A country, as parsed from a key of the stats country maps.
+///
+/// Requires the `isocountry` feature to be enabled.
+#[cfg(feature = "isocountry")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CountryCode {
+    /// A recognized ISO 3166-1 country.
+    Iso(isocountry::CountryCode),
+    /// A key that isn't a recognized country code or name.
+    Unknown(String),
+}
+
+#[cfg(feature = "isocountry")]
+impl CountryCode {
+    /// Parse an ISO 3166-1 alpha-2 code (`US`), alpha-3 code (`USA`) or English short name
+    /// (`United States of America`), ignoring case.
+    pub fn parse(key: &str) -> CountryCode {
+        let key = key.trim();
+        isocountry::CountryCode::for_alpha2_caseless(key)
+            .or_else(|_| isocountry::CountryCode::for_alpha3_caseless(key))
+            .ok()
+            .or_else(|| {
+                isocountry::CountryCode::iter()
+                    .find(|code| code.name().eq_ignore_ascii_case(key))
+                    .copied()
+            })
+            .map_or_else(|| CountryCode::Unknown(key.to_string()), CountryCode::Iso)
+    }
+}
+
+#[cfg(feature = "isocountry")]
+impl StatsResponse {
+    /// Clicks per country, keyed by parsed [`CountryCode`]s.
+    ///
+    /// Keys naming the same country (such as `US` and `USA`) are merged, and unrecognized keys
+    /// are kept as [`CountryCode::Unknown`].
+    ///
+    /// Requires the `isocountry` feature to be enabled.
+    pub fn countries_iso(&self) -> HashMap<CountryCode, u32> {
+        let mut countries = HashMap::new();
+        for (key, clicks) in self.country.iter().flatten() {
+            *countries.entry(CountryCode::parse(key)).or_insert(0) += clicks;
+        }
+        countries
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl StatsResponse {
+    /// Clicks per day as a continuous, chronologically sorted series.
+    ///
+    /// Days missing from `counter` are filled in with zero clicks, from the earliest to the latest
+    /// recorded day. Keys that aren't `YYYY-MM-DD` dates are ignored.
+    ///
+    /// Requires the `chrono` feature to be enabled.
+    pub fn daily_series_filled(&self) -> Vec<(NaiveDate, u32)> {
+        let days = dated_counter(&self.counter);
+        let (Some(&first), Some(&last)) = (days.keys().next(), days.keys().next_back()) else {
+            return Vec::new();
+        };
+
+        first
+            .iter_days()
+            .take_while(|day| *day <= last)
+            .map(|day| (day, days.get(&day).copied().unwrap_or(0)))
+            .collect()
+    }
+
+    /// Clicks per day from `counter`, keyed by parsed dates in chronological order.
+    ///
+    /// Keys that aren't `YYYY-MM-DD` dates are left out. Empty if there is no click data.
+    ///
+    /// Requires the `chrono` feature to be enabled.
+    pub fn counter_by_date(&self) -> BTreeMap<NaiveDate, u32> {
+        dated_counter(&self.counter)
+    }
+
+    /// The days of [`counter_by_date`](Self::counter_by_date) in chronological chunks of `days`
+    /// entries, such as for paging through years of daily clicks.
+    ///
+    /// Only days with recorded clicks are included, and the last chunk holds whatever is left, so
+    /// it may be shorter. Yields nothing if there is no click data, or if `days` is `0`.
+    ///
+    /// Requires the `chrono` feature to be enabled.
+    pub fn counter_chunks(&self, days: usize) -> impl Iterator<Item = Vec<(NaiveDate, u32)>> {
+        let mut entries = self.counter_by_date().into_iter();
+        std::iter::from_fn(move || {
+            if days == 0 {
+                return None;
+            }
+            let chunk: Vec<_> = entries.by_ref().take(days).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        })
+    }
+
+    /// Total clicks over the last `n` days of `counter`, counting back from its latest day.
+    ///
+    /// The latest recorded day is included, so `clicks_last_days(7)` covers that day and the six
+    /// before it. Returns `0` when there is no click data.
+    ///
+    /// Requires the `chrono` feature to be enabled.
+    pub fn clicks_last_days(&self, n: u32) -> u32 {
+        let days = dated_counter(&self.counter);
+        let Some(&last) = days.keys().next_back() else {
+            return 0;
+        };
+
+        days.iter()
+            .filter(|(day, _)| (last - **day).num_days() < i64::from(n))
+            .map(|(_, clicks)| clicks)
+            .sum()
+    }
+
+    /// `creation_date` parsed as a UTC timestamp.
+    ///
+    /// Returns `None` if it is missing, empty or in an unrecognized format. See
+    /// [`last_click_datetime`](Self::last_click_datetime) for the accepted formats.
+    ///
+    /// Requires the `chrono` feature to be enabled.
+    pub fn creation_datetime(&self) -> Option<DateTime<Utc>> {
+        parse_timestamp(self.creation_date.as_deref()?)
+    }
+
+    /// `last_click` parsed as a UTC timestamp.
+    ///
+    /// Accepts RFC 3339 timestamps, `YYYY-MM-DD HH:MM:SS` (as sent by spoo.me, in UTC) and plain
+    /// `YYYY-MM-DD` dates, which are taken as midnight. Returns `None` if it is missing, empty or
+    /// in any other format.
+    ///
+    /// Requires the `chrono` feature to be enabled.
+    pub fn last_click_datetime(&self) -> Option<DateTime<Utc>> {
+        parse_timestamp(self.last_click.as_deref()?)
+    }
+}
+
+/// Parse a timestamp sent by the API, assuming UTC when it carries no offset.
+#[cfg(feature = "chrono")]
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|day| day.and_time(NaiveTime::MIN))
+        })
+        .ok()
+        .map(|datetime| datetime.and_utc())
+}
+
+/// Parse the date keys of a per-day click map, skipping any that aren't `YYYY-MM-DD`.
+#[cfg(feature = "chrono")]
+fn dated_counter(counter: &Option<HashMap<String, u32>>) -> BTreeMap<NaiveDate, u32> {
+    counter
+        .iter()
+        .flatten()
+        .filter_map(|(day, clicks)| {
+            NaiveDate::parse_from_str(day, "%Y-%m-%d")
+                .ok()
+                .map(|day| (day, *clicks))
+        })
+        .collect()
+}
+
+/// Request struct for resolving where a short URL points, without following its redirect.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ResolveRequest {
+    #[serde(skip_serializing)]
+    pub(crate) short_code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) password: Option<String>,
+    #[serde(skip_serializing)]
+    pub(crate) correlation_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl ResolveRequest {
+    /// Create a ResolveRequest (optionally with password).
+    pub fn new<S: Into<String>>(short_code: S) -> Self {
+        ResolveRequest {
+            short_code: short_code.into(),
+            password: None,
+            correlation_id: None,
+            timeout: None,
+        }
+    }
+
+    /// Optional password for unlocking the short URL (if set).
+    pub fn password<P: Into<String>>(mut self, password: P) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Optional correlation ID, sent as the `X-Correlation-Id` header with this request only.
+    ///
+    /// Ignored if it isn't a valid header value.
+    pub fn correlation_id<C: Into<String>>(mut self, id: C) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+    /// Optional timeout for this request only, overriding the client's timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if !is_valid_short_code(&self.short_code) {
+            return Err(ValidationError::InvalidAliasFormat(self.short_code.clone()));
+        }
+        validate_password_opt(&self.password)
+    }
+
+    /// Headers specific to this request.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn headers(&self) -> HeaderMap {
+        request_headers(&self.correlation_id)
+    }
+}
+
+/// Where a short URL points, as found by resolving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveResponse {
+    /// The original long URL, or `None` if the short URL asked for a password instead.
+    pub url: Option<String>,
+    /// Whether the short URL answered with a password page instead of redirecting.
+    ///
+    /// A link unlocked with the right password redirects like any other, so this is only `true`
+    /// when no password was given.
+    pub password_protected: bool,
+}
+
+/// Where a short URL lands, as found by following its redirects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowResult {
+    /// Every URL visited, starting with the short URL and ending with `final_url`.
+    pub chain: Vec<String>,
+    /// The URL of the last response.
+    pub final_url: String,
+    /// The HTTP status code of the last response.
+    pub status: u16,
+    /// Whether following stopped at the redirect limit, while the last response still redirected.
+    pub max_redirects_reached: bool,
 }
 
 /// Enum representing the available export formats.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum ExportFormat {
     /// Export as JSON.
     JSON,
@@ -201,6 +1306,42 @@ pub enum ExportFormat {
     XML,
 }
 
+impl ExportFormat {
+    /// Every export format, for exporting a short URL in all of them.
+    pub const fn all() -> [ExportFormat; 4] {
+        [
+            ExportFormat::JSON,
+            ExportFormat::CSV,
+            ExportFormat::XLSX,
+            ExportFormat::XML,
+        ]
+    }
+
+    /// File extension of the exported data (CSV exports are zipped).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::JSON => "json",
+            ExportFormat::CSV => "zip",
+            ExportFormat::XLSX => "xlsx",
+            ExportFormat::XML => "xml",
+        }
+    }
+
+    /// The export format matching a `Content-Type` header value, if it is a known one.
+    pub(crate) fn from_content_type(content_type: &str) -> Option<ExportFormat> {
+        let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/json" => Some(ExportFormat::JSON),
+            "application/zip" | "application/x-zip-compressed" => Some(ExportFormat::CSV),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => {
+                Some(ExportFormat::XLSX)
+            }
+            "application/xml" | "text/xml" => Some(ExportFormat::XML),
+            _ => None,
+        }
+    }
+}
+
 impl Display for ExportFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -212,8 +1353,23 @@ impl Display for ExportFormat {
     }
 }
 
+impl FromStr for ExportFormat {
+    type Err = ParseExportFormatError;
+
+    /// Parse a format name as shown by [`Display`], ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(ExportFormat::JSON),
+            "csv" => Ok(ExportFormat::CSV),
+            "xlsx" => Ok(ExportFormat::XLSX),
+            "xml" => Ok(ExportFormat::XML),
+            _ => Err(ParseExportFormatError(s.to_string())),
+        }
+    }
+}
+
 /// Request payload for `POST /export/{shortCode}/{exportFormat}`.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ExportRequest {
     /// The short code of the URL to export.
     #[serde(skip_serializing)]
@@ -222,6 +1378,10 @@ pub struct ExportRequest {
     pub(crate) export_format: ExportFormat,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) password: Option<String>,
+    #[serde(skip_serializing)]
+    pub(crate) correlation_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) timeout: Option<Duration>,
 }
 
 impl ExportRequest {
@@ -231,26 +1391,68 @@ impl ExportRequest {
             short_code: short_code.into(),
             export_format,
             password: None,
+            correlation_id: None,
+            timeout: None,
         }
     }
 
+    /// Creates a new ExportRequest for a password-protected short URL.
+    ///
+    /// Shorthand for `ExportRequest::new(short_code, export_format).password(password)`.
+    pub fn with_password<S: Into<String>, P: Into<String>>(
+        short_code: S,
+        export_format: ExportFormat,
+        password: P,
+    ) -> Self {
+        Self::new(short_code, export_format).password(password)
+    }
+
     /// Optional password for accessing the export (if set on the short URL).
     pub fn password<P: Into<String>>(mut self, password: P) -> Self {
         self.password = Some(password.into());
         self
     }
+    /// Optional correlation ID, sent as the `X-Correlation-Id` header with this request only.
+    ///
+    /// Ignored if it isn't a valid header value.
+    pub fn correlation_id<C: Into<String>>(mut self, id: C) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+    /// Optional timeout for this request only, overriding the client's timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if !is_valid_short_code(&self.short_code) {
+            return Err(ValidationError::InvalidAliasFormat(self.short_code.clone()));
+        }
+        Ok(())
+    }
+
+    /// Headers specific to this request.
+    pub(crate) fn headers(&self) -> HeaderMap {
+        request_headers(&self.correlation_id)
+    }
 }
 
 /// Implementation for creating an export request.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExportResponse {
     /// The raw data returned
     pub(crate) data: Vec<u8>,
+    /// The format that was requested, if known.
+    pub(crate) format: Option<ExportFormat>,
+    /// The `Content-Type` of the response, if sent.
+    pub(crate) content_type: Option<String>,
 }
 
 impl ExportResponse {
     /// Writes the export data to a file at the specified path.
-    pub fn save_to_file(&self, path: &str) -> std::io::Result<()> {
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
         use std::fs::File;
         use std::io::Write;
 
@@ -263,4 +1465,308 @@ impl ExportResponse {
     pub fn data(&self) -> &[u8] {
         &self.data
     }
+
+    /// Returns the size of the export in bytes.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the export contains no data.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the format the export was requested in, if known.
+    pub fn format(&self) -> Option<&ExportFormat> {
+        self.format.as_ref()
+    }
+
+    /// Returns the `Content-Type` the export was sent with, if any.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Suggests a filesystem-safe filename for this export of `short_code`, such as `ga.xlsx`.
+    ///
+    /// The extension follows the `Content-Type` of the response when it is a known export type,
+    /// and the requested format otherwise. See [`safe_filename`] for how `short_code` is made
+    /// safe. If neither is known, the `bin` extension is used.
+    pub fn suggested_filename(&self, short_code: &str) -> String {
+        let format = self
+            .content_type
+            .as_deref()
+            .and_then(ExportFormat::from_content_type)
+            .or_else(|| self.format.clone());
+        match format {
+            Some(format) => safe_filename(short_code, &format),
+            None => format!("{}.bin", safe_file_stem(short_code)),
+        }
+    }
+
+    /// Parses a JSON export into `T`.
+    ///
+    /// Returns a [`UrlShortenerError::Json`] error if the data isn't valid JSON for `T`, which is
+    /// always the case for CSV, XLSX and XML exports.
+    pub fn as_json<T: DeserializeOwned>(&self) -> Result<T, UrlShortenerError> {
+        Ok(serde_json::from_slice(&self.data)?)
+    }
+
+    /// Parses a JSON export into a [`StatsResponse`].
+    ///
+    /// Returns a [`UrlShortenerError::Json`] error if the data isn't a JSON stats export.
+    pub fn as_stats(&self) -> Result<StatsResponse, UrlShortenerError> {
+        self.as_json()
+    }
+
+    /// Parses an XML export into a [`StatsResponse`].
+    ///
+    /// Returns a [`UrlShortenerError::Xml`] error if the data isn't an XML stats export.
+    ///
+    /// Requires the `xml` feature to be enabled.
+    #[cfg(feature = "xml")]
+    pub fn as_stats_from_xml(&self) -> Result<StatsResponse, UrlShortenerError> {
+        Ok(quick_xml::de::from_reader(self.data.as_slice())?)
+    }
+
+    /// Parses a CSV export into the records of each CSV file, keyed by file name.
+    ///
+    /// CSV exports are zip archives holding one CSV file per kind of stat; files in the archive
+    /// that aren't CSV files are skipped. An export that is a single CSV file rather than a zip
+    /// archive is keyed by `export.csv`. The header row of each file is kept as its first record.
+    ///
+    /// Returns a [`UrlShortenerError::Zip`] or [`UrlShortenerError::Csv`] error if the data isn't
+    /// a valid archive or CSV file.
+    ///
+    /// Requires the `export-parse` feature to be enabled.
+    #[cfg(feature = "export-parse")]
+    pub fn parse_csv(&self) -> Result<HashMap<String, Vec<csv::StringRecord>>, UrlShortenerError> {
+        if !self.data.starts_with(b"PK") {
+            let records = parse_csv_records(self.data.as_slice())?;
+            return Ok(HashMap::from([("export.csv".to_string(), records)]));
+        }
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(self.data.as_slice()))?;
+        let mut files = HashMap::new();
+        for i in 0..archive.len() {
+            let file = archive.by_index(i)?;
+            if !file.is_file() || !file.name().to_ascii_lowercase().ends_with(".csv") {
+                continue;
+            }
+            let name = file.name().to_string();
+            files.insert(name, parse_csv_records(file)?);
+        }
+        Ok(files)
+    }
+}
+
+/// Parse every row of a CSV file, including its header row.
+#[cfg(feature = "export-parse")]
+fn parse_csv_records<R: std::io::Read>(reader: R) -> Result<Vec<csv::StringRecord>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader)
+        .records()
+        .collect()
+}
+
+impl AsRef<[u8]> for ExportResponse {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl From<Vec<u8>> for ExportResponse {
+    fn from(data: Vec<u8>) -> Self {
+        ExportResponse {
+            data,
+            format: None,
+            content_type: None,
+        }
+    }
+}
+
+/// The image format of a rendered QR code.
+///
+/// Requires the `qr` feature to be enabled.
+#[cfg(feature = "qr")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QrFormat {
+    /// A PNG image.
+    #[default]
+    PNG,
+    /// An SVG document.
+    SVG,
+}
+
+/// Options for rendering a QR code of a short URL.
+///
+/// Requires the `qr` feature to be enabled.
+#[cfg(feature = "qr")]
+#[derive(Debug, Clone)]
+pub struct QrOptions {
+    pub(crate) size: u32,
+    pub(crate) format: QrFormat,
+}
+
+#[cfg(feature = "qr")]
+impl QrOptions {
+    /// Creates QrOptions for a 256x256 PNG.
+    pub fn new() -> Self {
+        QrOptions {
+            size: 256,
+            format: QrFormat::PNG,
+        }
+    }
+    /// Minimum width and height of the image, in pixels.
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+    /// Image format of the QR code.
+    pub fn format(mut self, format: QrFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+#[cfg(feature = "qr")]
+impl Default for QrOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A rendered QR code image.
+///
+/// Requires the `qr` feature to be enabled.
+#[cfg(feature = "qr")]
+#[derive(Debug, Clone)]
+pub struct QrResponse {
+    /// The encoded image.
+    pub(crate) data: Vec<u8>,
+    /// The format of the image.
+    pub(crate) format: QrFormat,
+}
+
+#[cfg(feature = "qr")]
+impl QrResponse {
+    /// Renders a QR code encoding `url`.
+    pub(crate) fn render(url: &str, options: &QrOptions) -> Result<Self, UrlShortenerError> {
+        let code = qrcode::QrCode::new(url.as_bytes())?;
+        let data = match options.format {
+            QrFormat::PNG => {
+                let image = code
+                    .render::<image::Luma<u8>>()
+                    .min_dimensions(options.size, options.size)
+                    .build();
+                let mut data = std::io::Cursor::new(Vec::new());
+                image.write_to(&mut data, image::ImageFormat::Png)?;
+                data.into_inner()
+            }
+            QrFormat::SVG => code
+                .render::<qrcode::render::svg::Color>()
+                .min_dimensions(options.size, options.size)
+                .build()
+                .into_bytes(),
+        };
+        Ok(QrResponse {
+            data,
+            format: options.format,
+        })
+    }
+
+    /// Writes the image to a file at the specified path.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, &self.data)
+    }
+
+    /// Returns the encoded image.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the size of the image in bytes.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the image contains no data.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the format of the image.
+    pub fn format(&self) -> QrFormat {
+        self.format
+    }
+}
+
+#[cfg(feature = "qr")]
+impl AsRef<[u8]> for QrResponse {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Request struct for `DELETE /{shortCode}`, used to delete a short URL.
+///
+/// Only supported by self-hosted instances. Requires the `custom_url` feature to be enabled.
+#[cfg(feature = "custom_url")]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct DeleteRequest {
+    #[serde(skip_serializing)]
+    pub(crate) short_code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) password: Option<String>,
+    #[serde(skip_serializing)]
+    pub(crate) correlation_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) timeout: Option<Duration>,
+}
+
+#[cfg(feature = "custom_url")]
+impl DeleteRequest {
+    /// Create a DeleteRequest (optionally with password).
+    pub fn new<S: Into<String>>(short_code: S) -> Self {
+        DeleteRequest {
+            short_code: short_code.into(),
+            password: None,
+            correlation_id: None,
+            timeout: None,
+        }
+    }
+
+    /// Optional password of the short URL (if set).
+    pub fn password<P: Into<String>>(mut self, password: P) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Optional correlation ID, sent as the `X-Correlation-Id` header with this request only.
+    ///
+    /// Ignored if it isn't a valid header value.
+    pub fn correlation_id<C: Into<String>>(mut self, id: C) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+    /// Optional timeout for this request only, overriding the client's timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Check the request against the same rules the client applies before sending it.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_password_opt(&self.password)?;
+        if !is_valid_short_code(&self.short_code) {
+            return Err(ValidationError::InvalidAliasFormat(self.short_code.clone()));
+        }
+        Ok(())
+    }
+
+    /// Headers specific to this request.
+    pub(crate) fn headers(&self) -> HeaderMap {
+        request_headers(&self.correlation_id)
+    }
 }
@@ -0,0 +1,92 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// How the client retries requests that fail with a retryable error, such as a rate limit.
+///
+/// The delay before retry `n` (starting at `0`) is `base_delay * 2^n`, capped at `max_delay`.
+/// With `jitter` enabled, a random amount of up to half that delay is taken off, so clients
+/// that were rate limited together don't all retry at the same moment.
+///
+/// Requests that fail with a transient HTTP error, such as a refused connection or a timeout (see
+/// [`UrlShortenerError::is_transient`](crate::errors::UrlShortenerError::is_transient)), are
+/// retried separately, up to `max_transient_retries` times with the same delays. Other errors,
+/// such as undecodable bodies or API errors other than a rate limit, are never retried as
+/// transient ones.
+///
+/// The default policy never retries.
+///
+/// # Example usage:
+/// ```rust
+/// use std::time::Duration;
+/// use spoo_me::{client::UrlShortenerClient, retry::RetryPolicy};
+///
+/// let client = UrlShortenerClient::new().with_retry_policy(RetryPolicy {
+///     max_retries: 3,
+///     base_delay: Duration::from_millis(250),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// How many times a request failing with a retryable API error is retried before giving up.
+    pub max_retries: u32,
+    /// How many times a request failing with a transient HTTP error is retried before giving up.
+    pub max_transient_retries: u32,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The upper bound for the delay between two attempts.
+    pub max_delay: Duration,
+    /// Whether to randomize delays.
+    pub jitter: bool,
+}
+
+impl RetryPolicy {
+    /// A policy retrying up to `max_retries` times with the default delays and jitter.
+    pub fn new(max_retries: u32) -> Self {
+        RetryPolicy {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// The delay to wait before retry number `retry`, counting from `0`.
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+
+        if self.jitter {
+            let max_jitter = delay.as_nanos() as u64 / 2;
+            let jitter = random_u64().checked_rem(max_jitter).unwrap_or(0);
+            delay - Duration::from_nanos(jitter)
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            max_transient_retries: 0,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+/// A random number from the standard library's randomly seeded hasher.
+fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
@@ -0,0 +1,101 @@
+#[cfg(not(feature = "blocking"))]
+use async_trait::async_trait;
+
+use crate::{
+    client::UrlShortenerClient,
+    errors::UrlShortenerError,
+    requests::{
+        EmojiRequest, EmojiResponse, ExportRequest, ExportResponse, ShortenRequest,
+        ShortenResponse, StatsRequest, StatsResponse,
+    },
+};
+
+/// The spoo.me endpoints, as a trait so code using them can be tested against a fake.
+///
+/// Implemented by [`UrlShortenerClient`]. Accept a `&dyn UrlShortener` or a generic
+/// `T: UrlShortener` to be able to swap in a mock.
+///
+/// ```rust
+/// use spoo_me::{errors::UrlShortenerError, requests::ShortenRequest, shortener::UrlShortener};
+///
+/// async fn share(shortener: &dyn UrlShortener, url: &str) -> Result<String, UrlShortenerError> {
+///     let response = shortener.shorten(ShortenRequest::new(url)).await?;
+///     Ok(format!("Check this out: {}", response.short_url))
+/// }
+/// ```
+#[cfg(not(feature = "blocking"))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait UrlShortener: Send + Sync {
+    /// Shorten a URL.
+    async fn shorten(&self, req: ShortenRequest) -> Result<ShortenResponse, UrlShortenerError>;
+
+    /// Create an emoji URL.
+    async fn emoji(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError>;
+
+    /// Get statistics for a shortened URL.
+    async fn stats(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError>;
+
+    /// Export data for a shortened URL.
+    async fn export(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError>;
+}
+
+#[cfg(not(feature = "blocking"))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl UrlShortener for UrlShortenerClient {
+    async fn shorten(&self, req: ShortenRequest) -> Result<ShortenResponse, UrlShortenerError> {
+        UrlShortenerClient::shorten(self, req).await
+    }
+
+    async fn emoji(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
+        UrlShortenerClient::emoji(self, req).await
+    }
+
+    async fn stats(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
+        UrlShortenerClient::stats(self, req).await
+    }
+
+    async fn export(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError> {
+        UrlShortenerClient::export(self, req).await
+    }
+}
+
+/// The spoo.me endpoints in blocking mode, as a trait so code using them can be tested against a
+/// fake.
+///
+/// Implemented by [`UrlShortenerClient`]. Accept a `&dyn BlockingUrlShortener` or a generic
+/// `T: BlockingUrlShortener` to be able to swap in a mock.
+#[cfg(feature = "blocking")]
+pub trait BlockingUrlShortener {
+    /// Shorten a URL.
+    fn shorten_blocking(&self, req: ShortenRequest) -> Result<ShortenResponse, UrlShortenerError>;
+
+    /// Create an emoji URL.
+    fn emoji_blocking(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError>;
+
+    /// Get statistics for a shortened URL.
+    fn stats_blocking(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError>;
+
+    /// Export data for a shortened URL.
+    fn export_blocking(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError>;
+}
+
+#[cfg(feature = "blocking")]
+impl BlockingUrlShortener for UrlShortenerClient {
+    fn shorten_blocking(&self, req: ShortenRequest) -> Result<ShortenResponse, UrlShortenerError> {
+        UrlShortenerClient::shorten_blocking(self, req)
+    }
+
+    fn emoji_blocking(&self, req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
+        UrlShortenerClient::emoji_blocking(self, req)
+    }
+
+    fn stats_blocking(&self, req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
+        UrlShortenerClient::stats_blocking(self, req)
+    }
+
+    fn export_blocking(&self, req: ExportRequest) -> Result<ExportResponse, UrlShortenerError> {
+        UrlShortenerClient::export_blocking(self, req)
+    }
+}
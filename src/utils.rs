@@ -1,38 +1,298 @@
-const URL_REGEX: &str = r#"^(ftp|http|https):\/\/[^ "]+$"#;
-const ALIAS_REGEX: &str = r"^[a-zA-Z0-9_-]*$";
+use std::sync::LazyLock;
+
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    errors::{PasswordViolation, ValidationError},
+    requests::ExportFormat,
+};
+
+// Compiled on first use and shared, so validating in a loop doesn't recompile them.
+static URL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^(ftp|http|https):\/\/[^ "]+$"#).unwrap());
+static ALIAS_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-zA-Z0-9_-]*$").unwrap());
 
 /// Validate password format (≥8 chars, contains letter, digit, '@' or '.', no consecutive special chars).
 pub fn is_valid_password(pw: &str) -> bool {
-    let len_ok = pw.len() >= 8;
-    let has_letter = pw.chars().any(|c| c.is_alphabetic());
-    let has_digit = pw.chars().any(|c| c.is_ascii_digit());
-    let has_special = pw.chars().any(|c| c == '@' || c == '.');
-    let no_consec =
-        !pw.contains("..") && !pw.contains("@@") && !pw.contains("@.") && !pw.contains(".@");
-    len_ok && has_letter && has_digit && has_special && no_consec
+    validate_password(pw).is_ok()
 }
 
-/// Validate URL format (http/https/ftp, no base url or ".." in path).
+/// Validate password format, returning the first requirement the password fails to meet.
+///
+/// See [`is_valid_password`] for the requirements.
+pub fn validate_password(pw: &str) -> Result<(), PasswordViolation> {
+    if pw.len() < 8 {
+        return Err(PasswordViolation::TooShort);
+    }
+    if !pw.chars().any(|c| c.is_alphabetic()) {
+        return Err(PasswordViolation::MissingLetter);
+    }
+    if !pw.chars().any(|c| c.is_ascii_digit()) {
+        return Err(PasswordViolation::MissingDigit);
+    }
+    if !pw.chars().any(|c| c == '@' || c == '.') {
+        return Err(PasswordViolation::MissingSpecial);
+    }
+    if pw.contains("..") || pw.contains("@@") || pw.contains("@.") || pw.contains(".@") {
+        return Err(PasswordViolation::ConsecutiveSpecial);
+    }
+    Ok(())
+}
+
+/// Validate a batch of passwords up front, returning one result per password in input order.
+pub fn validate_passwords(pws: &[&str]) -> Vec<Result<(), ValidationError>> {
+    pws.iter()
+        .map(|pw| validate_password(pw).map_err(ValidationError::InvalidPasswordFormat))
+        .collect()
+}
+
+/// Options controlling how [`normalize_url`] rewrites a target URL.
+///
+/// Requires the `url` feature to be enabled.
+#[cfg(feature = "url")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// Remove `utm_*` tracking parameters from the query string. Defaults to `false`.
+    pub strip_utm_params: bool,
+    /// Collapse repeated slashes in the path, so `/a//b` becomes `/a/b`. Defaults to `true`.
+    pub remove_duplicate_slashes: bool,
+}
+
+#[cfg(feature = "url")]
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        NormalizeOptions {
+            strip_utm_params: false,
+            remove_duplicate_slashes: true,
+        }
+    }
+}
+
+/// Normalize a URL so effectively identical URLs produce the same string.
+///
+/// The host is always lowercased (and an empty path becomes `/`); the remaining rewrites are
+/// controlled by `options`. Returns `None` if `url` can't be parsed.
+///
+/// Requires the `url` feature to be enabled.
+#[cfg(feature = "url")]
+pub fn normalize_url(url: &str, options: &NormalizeOptions) -> Option<String> {
+    let mut url = reqwest::Url::parse(url).ok()?;
+
+    if options.remove_duplicate_slashes && url.path().contains("//") {
+        let mut path = url.path().to_string();
+        while path.contains("//") {
+            path = path.replace("//", "/");
+        }
+        url.set_path(&path);
+    }
+
+    if options.strip_utm_params && url.query().is_some() {
+        let kept: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| !key.starts_with("utm_"))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        if kept.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(kept);
+        }
+    }
+
+    Some(url.into())
+}
+
+/// Percent-encode the path and query of a URL containing characters that must be escaped, such as
+/// spaces or non-ASCII text.
+///
+/// URLs that need no escaping, including ones that are already percent-encoded, are returned
+/// unchanged, so nothing is encoded twice. Otherwise the URL is re-serialized, which can also
+/// change its form in other ways, such as turning a Unicode host into punycode. URLs that can't be
+/// parsed are returned unchanged, to be rejected by validation.
+pub fn percent_encode_url(url: &str) -> String {
+    if !url.chars().any(needs_escaping) {
+        return url.to_string();
+    }
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => parsed.into(),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Whether `c` can't appear unescaped in a URL.
+fn needs_escaping(c: char) -> bool {
+    !c.is_ascii_graphic() || matches!(c, '"' | '<' | '>' | '`' | '{' | '}')
+}
+
+/// Validate URL format (http/https, no base url or ".." in path).
+///
+/// FTP URLs are rejected, as spoo.me only shortens web URLs. Use [`is_valid_url_allowing_ftp`]
+/// for instances that accept them.
 #[cfg(feature = "custom_url")]
 pub fn is_valid_url(url: &str, base_url: &str) -> bool {
-    let re = regex::Regex::new(URL_REGEX).unwrap();
-    re.is_match(url) && !url.contains(base_url) && !url.contains("..")
+    !is_ftp_url(url) && is_valid_url_allowing_ftp(url, base_url)
 }
 
-/// Validate URL format (http/https/ftp, no "spoo.me" or ".." in path).
+/// Validate URL format (http/https/ftp, no base url or ".." in path).
+#[cfg(feature = "custom_url")]
+pub fn is_valid_url_allowing_ftp(url: &str, base_url: &str) -> bool {
+    URL_REGEX.is_match(url) && !url.contains(base_url) && !url.contains("..")
+}
+
+/// Validate URL format (http/https, no "spoo.me" or ".." in path).
+///
+/// FTP URLs are rejected, as spoo.me only shortens web URLs. Use [`is_valid_url_allowing_ftp`]
+/// for instances that accept them.
 #[cfg(not(feature = "custom_url"))]
 pub fn is_valid_url(url: &str) -> bool {
-    let re = regex::Regex::new(URL_REGEX).unwrap();
-    re.is_match(url) && !url.contains("spoo.me") && !url.contains("..")
+    !is_ftp_url(url) && is_valid_url_allowing_ftp(url)
+}
+
+/// Validate URL format (http/https/ftp, no "spoo.me" or ".." in path).
+#[cfg(not(feature = "custom_url"))]
+pub fn is_valid_url_allowing_ftp(url: &str) -> bool {
+    URL_REGEX.is_match(url) && !url.contains("spoo.me") && !url.contains("..")
+}
+
+/// Whether `url` uses the `ftp` scheme.
+fn is_ftp_url(url: &str) -> bool {
+    url.starts_with("ftp://")
 }
 
 /// Validate alias format (alphanumeric, underscores, hyphens, max 16 chars).
 pub fn is_valid_alias(alias: &str) -> bool {
-    let re = regex::Regex::new(ALIAS_REGEX).unwrap();
-    re.is_match(alias) && !alias.is_empty() && alias.len() <= 16
+    ALIAS_REGEX.is_match(alias) && !alias.is_empty() && alias.len() <= 16
+}
+
+/// Aliases taken by routes of the spoo.me API and site, which the API rejects with an
+/// [`AliasError`](crate::errors::ApiError::AliasError).
+pub const RESERVED_ALIASES: &[&str] = &[
+    "api", "contact", "docs", "emoji", "export", "health", "legal", "metric", "report", "result",
+    "static", "stats",
+];
+
+/// Whether `alias` collides with a route name in [`RESERVED_ALIASES`].
+pub fn is_reserved_alias(alias: &str) -> bool {
+    RESERVED_ALIASES.contains(&alias)
+}
+
+/// Validate a short code, which is either an alias or an emoji sequence from the emoji endpoint.
+pub fn is_valid_short_code(code: &str) -> bool {
+    if code.is_ascii() {
+        is_valid_alias(code)
+    } else {
+        is_valid_emoji(code)
+    }
 }
 
 /// Validate max-clicks (must be a positive integer).
 pub fn is_valid_max_clicks(max: u32) -> bool {
     max > 0
 }
+
+/// Validate an emoji sequence (non-empty, made up only of emoji grapheme clusters).
+///
+/// Clusters such as flags, keycaps, skin tone variants and ZWJ sequences count as a single
+/// emoji, while plain text and whitespace are rejected.
+pub fn is_valid_emoji(seq: &str) -> bool {
+    !seq.is_empty() && seq.graphemes(true).all(is_emoji_grapheme)
+}
+
+/// The fewest emoji an emoji sequence may contain by default.
+pub const MIN_EMOJI_COUNT: usize = 1;
+
+/// The most emoji an emoji sequence may contain by default, as more are rejected by spoo.me with
+/// an [`EmojiError`](crate::errors::ApiError::EmojiError).
+pub const MAX_EMOJI_COUNT: usize = 15;
+
+/// The number of emoji in `seq`, counting each grapheme cluster (such as a flag or a ZWJ
+/// sequence) as one.
+pub fn emoji_count(seq: &str) -> usize {
+    seq.graphemes(true).count()
+}
+
+/// Whether `seq` contains between `min` and `max` emoji, inclusive. See [`emoji_count`].
+pub fn is_valid_emoji_count(seq: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&emoji_count(seq))
+}
+
+/// Whether a grapheme cluster is a single emoji.
+fn is_emoji_grapheme(grapheme: &str) -> bool {
+    let mut chars = grapheme.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    // Keycaps such as `1️⃣` start with a plain ASCII character.
+    let keycap = matches!(first, '0'..='9' | '#' | '*') && grapheme.contains('\u{20E3}');
+    (is_emoji_char(first) || keycap) && chars.all(|c| is_emoji_char(c) || is_emoji_component(c))
+}
+
+/// Whether `c` is a pictographic emoji code point.
+fn is_emoji_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x00A9
+            | 0x00AE
+            | 0x203C
+            | 0x2049
+            | 0x2122
+            | 0x2139
+            | 0x2194..=0x21AA
+            | 0x231A..=0x23FF
+            | 0x24C2
+            | 0x25AA..=0x25FE
+            | 0x2600..=0x27BF
+            | 0x2934..=0x2935
+            | 0x2B05..=0x2B55
+            | 0x3030
+            | 0x303D
+            | 0x3297
+            | 0x3299
+            | 0x1F000..=0x1FAFF
+    )
+}
+
+/// Whether `c` only modifies or joins the emoji before it.
+fn is_emoji_component(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x200D | 0x20E3 | 0xFE0E | 0xFE0F | 0xE0020..=0xE007F
+    )
+}
+
+/// Names that are reserved by Windows regardless of extension.
+const RESERVED_FILENAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Derive a filesystem-safe filename for an export of `short_code` in `format`.
+///
+/// ASCII letters, digits and `-` are kept as is, while anything else (such as the emoji of an
+/// emoji short code) is replaced by its hex code point between two `_`, so `🔥` becomes
+/// `_1f525_` and `_` itself becomes `_5f_`. Distinct short codes never share a filename. The
+/// extension matching `format` is appended.
+pub fn safe_filename(short_code: &str, format: &ExportFormat) -> String {
+    format!("{}.{}", safe_file_stem(short_code), format.extension())
+}
+
+/// The filesystem-safe stem used by [`safe_filename`].
+pub(crate) fn safe_file_stem(short_code: &str) -> String {
+    let mut stem = String::with_capacity(short_code.len());
+    for c in short_code.chars() {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            stem.push(c);
+        } else {
+            stem.push_str(&format!("_{:x}_", c as u32));
+        }
+    }
+
+    if stem.is_empty() {
+        stem.push_str("export");
+    } else if RESERVED_FILENAMES.contains(&stem.to_ascii_lowercase().as_str()) {
+        stem.insert(0, '_');
+    }
+
+    stem
+}
@@ -23,8 +23,8 @@ mod blocking_tests {
 
         let shortened_url = response.unwrap();
         assert!(
-            shortened_url.short_url.starts_with("https://spoo.me/"),
-            "Shortened URL does not start with expected base URL"
+            shortened_url.is_on(client.base_url()),
+            "Shortened URL is not on the client's base URL"
         );
     }
 
@@ -45,8 +45,8 @@ mod blocking_tests {
 
         let emoji_url = response.unwrap();
         assert!(
-            emoji_url.short_url.starts_with("https://spoo.me/"),
-            "Emoji URL does not start with expected base URL"
+            emoji_url.is_on(client.base_url()),
+            "Emoji URL is not on the client's base URL"
         );
     }
 
@@ -128,4 +128,36 @@ mod blocking_tests {
             response.err()
         );
     }
+
+    #[cfg(feature = "live_tests")]
+    #[test]
+    fn test_shorten_then_stats() {
+        let client = UrlShortenerClient::new();
+        let request = ShortenRequest::new("https://example.com").password("Test@123");
+
+        let response = client.shorten_then_stats_blocking(request);
+        assert!(
+            response.is_ok(),
+            "Failed to shorten then get stats: {:?}",
+            response.err()
+        );
+
+        let (shortened, stats) = response.unwrap();
+        assert_eq!(
+            shortened.short_code().as_deref(),
+            Some(stats.short_code.as_str())
+        );
+    }
+
+    #[cfg(feature = "live_tests")]
+    #[test]
+    fn test_health() {
+        let client = UrlShortenerClient::new();
+        let response = client.health_blocking();
+        assert!(
+            response.is_ok(),
+            "Health check failed: {:?}",
+            response.err()
+        );
+    }
 }
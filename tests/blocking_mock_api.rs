@@ -0,0 +1,766 @@
+#[cfg(all(feature = "custom_url", feature = "blocking"))]
+#[cfg(test)]
+mod blocking_mock_tests {
+    use spoo_me::{
+        client::{UrlShortenerClient, MAX_REDIRECTS},
+        errors::{ApiError, UrlShortenerError, ValidationError},
+        requests::{
+            DeleteRequest, EmojiRequest, ExportFormat, ExportRequest, ResolveRequest,
+            ShortenRequest, StatsRequest,
+        },
+        retry::RetryPolicy,
+    };
+    use std::time::Duration;
+
+    fn fast_retries(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_retries_rate_limit_by_default() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/emoji")
+            .with_status(429)
+            .expect(3)
+            .create();
+
+        let client =
+            UrlShortenerClient::new_with_base_url(server.url()).with_retry_policy(fast_retries(2));
+        let response = client.emoji_blocking(EmojiRequest::new("https://example.com"));
+
+        mock.assert();
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { .. }))
+            ),
+            "Expected rate limit error, got: {:?}",
+            response
+        );
+    }
+
+    #[test]
+    fn test_retries_transient_errors_blocking() {
+        // Nothing listens on the port until the server below starts, so the first attempt fails
+        // to connect and only the retry reaches the server.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let client = UrlShortenerClient::new_with_base_url(format!("http://127.0.0.1:{port}"))
+            .with_retry_policy(RetryPolicy {
+                max_transient_retries: 1,
+                base_delay: Duration::from_millis(500),
+                jitter: false,
+                ..Default::default()
+            });
+
+        let server = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            let mut server = mockito::Server::new_with_opts(mockito::ServerOpts {
+                port,
+                ..Default::default()
+            });
+            let mock = server
+                .mock("POST", "/")
+                .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+                .expect(1)
+                .create();
+            (server, mock)
+        });
+        let response = client.shorten_blocking(ShortenRequest::new("https://example.com"));
+
+        let (_server, mock) = server.join().unwrap();
+        mock.assert();
+        assert_eq!(response.unwrap().short_url, "https://spoo.me/abc");
+    }
+
+    #[test]
+    fn test_verify_redirect() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/abc")
+            .with_status(302)
+            .with_header("Location", "https://example.com/target")
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let short_url = format!("{}/abc", server.url());
+
+        assert!(client
+            .verify_redirect_blocking(&short_url, "https://example.com/target")
+            .unwrap());
+        assert!(!client
+            .verify_redirect_blocking(&short_url, "https://example.com/other")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_timeout() {
+        // Non-routable address, so the connection attempt hangs until the timeout.
+        let mut client = UrlShortenerClient::new_with_base_url("http://10.255.255.1");
+        client.set_timeout(Duration::from_millis(500));
+
+        let started = std::time::Instant::now();
+        let response = client.stats_blocking(StatsRequest::new("ga"));
+
+        assert!(
+            matches!(response, Err(UrlShortenerError::Http(_))),
+            "Expected an HTTP error, got: {:?}",
+            response
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_with_blocking_client() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/stats/ga")
+            .match_header("X-App", "my-app")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create();
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("X-App", "my-app".parse().unwrap());
+        let http = reqwest::blocking::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let mut client = UrlShortenerClient::with_blocking_client(http);
+        client.set_base_url(server.url());
+        let response = client.stats_blocking(StatsRequest::new("ga"));
+
+        mock.assert();
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+    }
+
+    #[test]
+    fn test_delete_blocking() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("DELETE", "/ga")
+            .match_header("content-type", mockito::Matcher::Missing)
+            .with_status(200)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.delete_blocking(DeleteRequest::new("ga"));
+
+        mock.assert();
+        assert!(response.is_ok(), "Failed to delete: {:?}", response.err());
+    }
+
+    #[test]
+    fn test_shorten_many_blocking() {
+        let mut server = mockito::Server::new();
+        let ok = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex("url=https%3A%2F%2Fexample.com".into()))
+            .with_body(r#"{"short_url": "https://spoo.me/ok", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .expect(3)
+            .create();
+        let failing = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(
+                "url=https%3A%2F%2Ffail.example".into(),
+            ))
+            .with_status(400)
+            .with_body(r#"{"error": "UrlError"}"#)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let requests = vec![
+            ShortenRequest::new("https://example.com/1"),
+            ShortenRequest::new("https://fail.example/2"),
+            ShortenRequest::new("https://example.com/3"),
+            ShortenRequest::new("https://example.com/4"),
+        ];
+        let results = client.shorten_many_blocking(requests, 3);
+
+        ok.assert();
+        failing.assert();
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(UrlShortenerError::Api(ApiError::UrlError))
+        ));
+        assert!(results[2].is_ok());
+        assert!(results[3].is_ok());
+        assert!(client.shorten_many_blocking(Vec::new(), 3).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_blocking() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/ga")
+            .with_status(302)
+            .with_header("Location", "https://google.com")
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.resolve_blocking(ResolveRequest::new("ga")).unwrap();
+
+        mock.assert();
+        assert_eq!(response.url.as_deref(), Some("https://google.com"));
+        assert!(!response.password_protected);
+    }
+
+    #[test]
+    fn test_export_to_writer_blocking() {
+        let body: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/export/ga/xlsx")
+            .with_body(&body)
+            .expect(2)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        let mut buffer = Vec::new();
+        let written = client
+            .export_to_writer_blocking(ExportRequest::new("ga", ExportFormat::XLSX), &mut buffer)
+            .expect("Failed to export to buffer");
+
+        let path = std::env::temp_dir().join(format!(
+            "spoo-me-export-blocking-{}.xlsx",
+            std::process::id()
+        ));
+        let mut file = std::fs::File::create(&path).unwrap();
+        let file_written = client
+            .export_to_writer_blocking(ExportRequest::new("ga", ExportFormat::XLSX), &mut file)
+            .expect("Failed to export to file");
+        let file_len = std::fs::metadata(&path).unwrap().len();
+        std::fs::remove_file(&path).unwrap();
+
+        mock.assert();
+        assert_eq!(buffer, body);
+        assert_eq!(written, body.len() as u64);
+        assert_eq!(file_written, written);
+        assert_eq!(file_len, written);
+    }
+
+    #[test]
+    fn test_emoji_validation_blocking() {
+        let mut server = mockito::Server::new();
+        let mock = server.mock("POST", "/emoji").expect(0).create();
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        let response =
+            client.emoji_blocking(EmojiRequest::new("https://example.com").emojies("abc"));
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Validation(
+                ValidationError::InvalidEmojiSequence(_)
+            ))
+        ));
+
+        let response =
+            client.emoji_blocking(EmojiRequest::new("https://example.com").max_clicks(0));
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Validation(
+                ValidationError::InvalidMaxClicks(0)
+            ))
+        ));
+
+        mock.assert();
+    }
+
+    #[test]
+    fn test_base_url_with_path_prefix_blocking() {
+        let mut server = mockito::Server::new();
+        let stats = server
+            .mock("POST", "/spoo/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .expect(2)
+            .create();
+
+        for base_url in [
+            format!("{}/spoo", server.url()),
+            format!("{}/spoo/", server.url()),
+        ] {
+            let client = UrlShortenerClient::new_with_base_url(base_url.as_str());
+            let response = client.stats_blocking(StatsRequest::new("ga"));
+            assert!(response.is_ok(), "{}: {:?}", base_url, response.err());
+        }
+
+        stats.assert();
+    }
+
+    #[test]
+    fn test_empty_short_code_blocking() {
+        let mut server = mockito::Server::new();
+        let any = server
+            .mock("POST", mockito::Matcher::Any)
+            .expect(0)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats_blocking(StatsRequest::new(""));
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(ValidationError::InvalidAliasFormat(ref code)))
+                    if code.is_empty()
+            ),
+            "Expected invalid alias error, got: {:?}",
+            response
+        );
+        let response = client.export_blocking(ExportRequest::new("", ExportFormat::JSON));
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(ValidationError::InvalidAliasFormat(ref code)))
+                    if code.is_empty()
+            ),
+            "Expected invalid alias error, got: {:?}",
+            response
+        );
+
+        any.assert();
+    }
+
+    #[test]
+    fn test_export_all_blocking() {
+        let mut server = mockito::Server::new();
+        let mut mocks = Vec::new();
+        for format in ["json", "csv", "xlsx"] {
+            mocks.push(
+                server
+                    .mock("POST", format!("/export/ga/{}", format).as_str())
+                    .with_body(format)
+                    .create(),
+            );
+        }
+        mocks.push(
+            server
+                .mock("POST", "/export/ga/xml")
+                .with_status(500)
+                .with_body(r#"{"error": "export failed"}"#)
+                .create(),
+        );
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let results = client.export_all_blocking("ga", None);
+
+        for mock in &mocks {
+            mock.assert();
+        }
+        assert_eq!(results.len(), 4);
+        for format in [ExportFormat::JSON, ExportFormat::CSV, ExportFormat::XLSX] {
+            let export = results[&format].as_ref().expect("Export failed");
+            assert_eq!(export.data(), format.to_string().as_bytes());
+        }
+        assert!(results[&ExportFormat::XML].is_err());
+    }
+
+    #[test]
+    fn test_shorten_then_stats_blocking() {
+        let mut server = mockito::Server::new();
+        let shorten = server
+            .mock("POST", "/")
+            .with_body(r#"{"short_url": "https://spoo.me/fresh", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create();
+        let stats = server
+            .mock("POST", "/stats/fresh")
+            .with_body(r#"{"short_code": "fresh", "url": "https://example.com", "total-clicks": 0, "total_unique_clicks": 0}"#)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response =
+            client.shorten_then_stats_blocking(ShortenRequest::new("https://example.com"));
+
+        shorten.assert();
+        stats.assert();
+        let (_, stats) = response.expect("Failed to shorten then get stats");
+        assert_eq!(stats.short_code, "fresh");
+    }
+
+    #[test]
+    fn test_stats_cache_blocking() {
+        let mut server = mockito::Server::new();
+        let stats = server
+            .mock("POST", "/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .expect(1)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url())
+            .with_stats_cache(Duration::from_secs(60));
+        for _ in 0..3 {
+            let response = client.stats_blocking(StatsRequest::new("ga"));
+            assert!(
+                response.is_ok(),
+                "Failed to get stats: {:?}",
+                response.err()
+            );
+        }
+
+        stats.assert();
+    }
+
+    #[test]
+    fn test_max_clicks_limit_blocking() {
+        let mut server = mockito::Server::new();
+        let shorten = server.mock("POST", "/").expect(0).create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url()).with_max_clicks_limit(10);
+        let response =
+            client.shorten_blocking(ShortenRequest::new("https://example.com").max_clicks(11));
+
+        shorten.assert();
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Validation(
+                ValidationError::MaxClicksAboveLimit {
+                    max_clicks: 11,
+                    limit: 10
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_request_timeout_blocking() {
+        let mut server = mockito::Server::new();
+        let slow = server
+            .mock("POST", "/stats/ga")
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_millis(500));
+                w.write_all(b"{}")
+            })
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response =
+            client.stats_blocking(StatsRequest::new("ga").timeout(Duration::from_millis(50)));
+
+        slow.assert();
+        assert!(
+            matches!(response, Err(UrlShortenerError::Http(ref err)) if err.is_timeout()),
+            "Expected timeout error, got: {:?}",
+            response
+        );
+    }
+
+    #[test]
+    fn test_follow() {
+        let mut server = mockito::Server::new();
+        let short = server
+            .mock("GET", "/abc")
+            .with_status(302)
+            .with_header("location", &format!("{}/landing", server.url()))
+            .create();
+        let landing = server.mock("GET", "/landing").with_status(404).create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let result = client.follow_blocking("abc").unwrap();
+
+        short.assert();
+        landing.assert();
+        assert_eq!(
+            result.chain,
+            vec![
+                format!("{}/abc", server.url()),
+                format!("{}/landing", server.url())
+            ]
+        );
+        assert_eq!(result.final_url, format!("{}/landing", server.url()));
+        assert_eq!(result.status, 404);
+        assert!(!result.max_redirects_reached);
+    }
+
+    #[test]
+    fn test_follow_max_redirects() {
+        let mut server = mockito::Server::new();
+        let looping = server
+            .mock("GET", "/abc")
+            .with_status(302)
+            .with_header("location", &format!("{}/abc", server.url()))
+            .expect(MAX_REDIRECTS + 1)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let result = client.follow_blocking("abc").unwrap();
+
+        looping.assert();
+        assert_eq!(result.chain.len(), MAX_REDIRECTS + 1);
+        assert_eq!(result.status, 302);
+        assert!(result.max_redirects_reached);
+    }
+
+    #[test]
+    fn test_follow_not_found() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/abc")
+            .with_status(404)
+            .with_body("<html>Not Found</html>")
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let result = client.follow_blocking("abc");
+
+        mock.assert();
+        assert!(matches!(
+            result,
+            Err(UrlShortenerError::Unexpected { status: 404, .. })
+        ));
+    }
+
+    #[test]
+    fn test_rate_limit_callback() {
+        use std::sync::{Arc, Mutex};
+
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/emoji")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(2)
+            .create();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&seen);
+        let client = UrlShortenerClient::new_with_base_url(server.url())
+            .with_retry_policy(fast_retries(1))
+            .with_rate_limit_callback(move |retry_after| {
+                recorder.lock().unwrap().push(retry_after);
+            });
+        let response = client.emoji_blocking(EmojiRequest::new("https://example.com"));
+
+        mock.assert();
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { .. }))
+        ));
+        assert_eq!(*seen.lock().unwrap(), vec![Some(Duration::ZERO); 2]);
+    }
+
+    #[test]
+    fn test_shorten_with_headers() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_header("x-ratelimit-remaining", "42")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let (response, headers) = client
+            .shorten_with_headers_blocking(ShortenRequest::new("https://example.com"))
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(response.short_url, "https://spoo.me/abc");
+        assert_eq!(headers["x-ratelimit-remaining"], "42");
+    }
+
+    #[test]
+    fn test_idempotency_key_header() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_header("idempotency-key", "key-123")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.shorten_blocking(
+            ShortenRequest::new("https://example.com").idempotency_key("key-123"),
+        );
+
+        mock.assert();
+        assert!(
+            response.is_ok(),
+            "Failed to shorten URL: {:?}",
+            response.err()
+        );
+    }
+
+    #[test]
+    fn test_warmup_blocking() {
+        let mut server = mockito::Server::new();
+        let mock = server.mock("HEAD", "/").expect(1).create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.warmup_blocking();
+
+        mock.assert();
+        assert!(response.is_ok(), "Warmup failed: {:?}", response.err());
+    }
+
+    #[test]
+    fn test_health() {
+        let mut server = mockito::Server::new();
+        let healthy = server
+            .mock("GET", "/")
+            .with_body("<html>spoo.me</html>")
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.health_blocking();
+
+        healthy.assert();
+        assert!(
+            response.is_ok(),
+            "Health check failed: {:?}",
+            response.err()
+        );
+
+        healthy.remove();
+        let rate_limited = server.mock("GET", "/").with_status(429).create();
+
+        let response = client.health_blocking();
+
+        rate_limited.assert();
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_default_request_options() {
+        use mockito::Matcher;
+
+        let mut server = mockito::Server::new();
+        let defaulted = server
+            .mock("POST", "/")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("max-clicks".into(), "10".into()),
+                Matcher::UrlEncoded("block-bots".into(), "true".into()),
+            ]))
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create();
+        let explicit = server
+            .mock("POST", "/emoji")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("max-clicks".into(), "5".into()),
+                Matcher::UrlEncoded("block-bots".into(), "false".into()),
+            ]))
+            .with_body(r#"{"short_url": "https://spoo.me/🔥", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create();
+
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .default_max_clicks(10)
+            .default_block_bots(true)
+            .build();
+        let shortened = client.shorten_blocking(ShortenRequest::new("https://example.com"));
+        let emoji = client.emoji_blocking(
+            EmojiRequest::new("https://example.com")
+                .max_clicks(5)
+                .block_bots(false),
+        );
+
+        defaulted.assert();
+        explicit.assert();
+        assert!(
+            shortened.is_ok(),
+            "Failed to shorten URL: {:?}",
+            shortened.err()
+        );
+        assert!(
+            emoji.is_ok(),
+            "Failed to create emoji URL: {:?}",
+            emoji.err()
+        );
+    }
+
+    #[test]
+    fn test_shorten_simple() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("POST", "/")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create();
+        server
+            .mock("POST", "/emoji")
+            .with_body(r#"{"short_url": "https://spoo.me/🔥", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        assert_eq!(
+            client
+                .shorten_simple_blocking("https://example.com")
+                .unwrap(),
+            "https://spoo.me/abc"
+        );
+        assert_eq!(
+            client.emoji_simple_blocking("https://example.com").unwrap(),
+            "https://spoo.me/🔥"
+        );
+    }
+
+    #[test]
+    fn test_stats_raw_blocking() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("POST", "/stats/ga")
+            .with_body(
+                r#"{"short_code": "ga", "url": "https://google.com", "clicks": {"total": 1}}"#,
+            )
+            .expect(2)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats_blocking(StatsRequest::new("ga"));
+        assert!(
+            matches!(response, Err(UrlShortenerError::Json(_))),
+            "Expected a JSON error, got: {:?}",
+            response
+        );
+
+        let raw = client.stats_raw_blocking(StatsRequest::new("ga")).unwrap();
+        assert_eq!(raw["short_code"], "ga");
+        assert_eq!(raw["clicks"]["total"], 1);
+    }
+
+    #[test]
+    fn test_requires_password_blocking() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("POST", "/stats/secret")
+            .with_status(401)
+            .with_body(r#"{"error": "PasswordError"}"#)
+            .create();
+        server
+            .mock("POST", "/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create();
+        server
+            .mock("POST", "/stats/missing")
+            .with_status(404)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        assert!(client.requires_password_blocking("secret").unwrap());
+        assert!(!client.requires_password_blocking("ga").unwrap());
+        let missing = client.requires_password_blocking("missing");
+        assert!(
+            matches!(
+                missing,
+                Err(UrlShortenerError::Unexpected { status: 404, .. })
+            ),
+            "Expected a 404, got: {:?}",
+            missing
+        );
+    }
+}
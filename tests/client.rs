@@ -0,0 +1,100 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use spoo_me::client::UrlShortenerClient;
+
+#[test]
+fn test_sensitive_headers() {
+    let client =
+        UrlShortenerClient::new().with_sensitive_header(HeaderName::from_static("x-session-token"));
+
+    assert!(client.is_sensitive_header(&AUTHORIZATION));
+    assert!(client.is_sensitive_header(&HeaderName::from_static("x-api-key")));
+    assert!(client.is_sensitive_header(&HeaderName::from_static("x-session-token")));
+    assert!(!client.is_sensitive_header(&HeaderName::from_static("x-correlation-id")));
+}
+
+#[test]
+fn test_redact_headers() {
+    let client = UrlShortenerClient::new();
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+    headers.insert("x-correlation-id", HeaderValue::from_static("trace-123"));
+
+    let redacted = client.redact_headers(&headers);
+
+    assert_eq!(redacted[AUTHORIZATION], "[REDACTED]");
+    assert_eq!(redacted["x-correlation-id"], "trace-123");
+    assert!(!format!("{:?}", redacted).contains("secret"));
+}
+
+#[test]
+fn test_new_with_timeout() {
+    let client = UrlShortenerClient::new_with_timeout(std::time::Duration::from_secs(5));
+    assert!(format!("{:?}", client).contains("timeout: Some(5s)"));
+}
+
+#[test]
+fn test_builder() {
+    let client = UrlShortenerClient::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .user_agent("my-app/1.0")
+        .retry_policy(spoo_me::retry::RetryPolicy::new(3))
+        .build();
+    let debug = format!("{:?}", client);
+
+    assert!(debug.contains("timeout: Some(5s)"));
+    assert!(debug.contains("user_agent: \"my-app/1.0\""));
+    assert!(debug.contains("max_retries: 3"));
+}
+
+#[test]
+fn test_default_user_agent() {
+    let client = UrlShortenerClient::new();
+    let debug = format!("{:?}", client);
+
+    assert!(spoo_me::client::DEFAULT_USER_AGENT.starts_with("spoo-me-rust-sdk/"));
+    assert!(debug.contains(spoo_me::client::DEFAULT_USER_AGENT));
+}
+
+fn _assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_send_sync() {
+    _assert_send_sync::<UrlShortenerClient>();
+    _assert_send_sync::<spoo_me::client::UrlShortenerClientBuilder>();
+    _assert_send_sync::<spoo_me::errors::UrlShortenerError>();
+}
+
+#[test]
+fn test_clone() {
+    let client = UrlShortenerClient::builder()
+        .user_agent("my-app/1.0")
+        .build();
+    let clones: Vec<UrlShortenerClient> = (0..1000).map(|_| client.clone()).collect();
+    assert!(clones
+        .iter()
+        .all(|clone| format!("{:?}", clone) == format!("{:?}", client)));
+
+    let mut changed = client.clone();
+    changed.set_user_agent("other-app/2.0");
+    assert!(format!("{:?}", changed).contains("other-app/2.0"));
+    assert!(format!("{:?}", client).contains("my-app/1.0"));
+}
+
+#[test]
+fn test_builder_connection_options() {
+    let client = UrlShortenerClient::builder()
+        .pool_max_idle_per_host(4)
+        .build();
+    assert!(format!("{:?}", client).contains("pool_max_idle_per_host: Some(4)"));
+
+    let client = UrlShortenerClient::builder()
+        .pool_idle_timeout(Some(std::time::Duration::from_secs(30)))
+        .build();
+    assert!(format!("{:?}", client).contains("pool_idle_timeout: Some(Some(30s))"));
+
+    let mut client = UrlShortenerClient::builder()
+        .http2_prior_knowledge()
+        .build();
+    client.set_timeout(std::time::Duration::from_secs(5));
+    assert!(format!("{:?}", client).contains("http2_prior_knowledge: true"));
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use spoo_me::errors::{ApiError, UrlShortenerError};
+
+#[test]
+fn test_rate_limit_display() {
+    assert_eq!(
+        ApiError::RateLimitExceeded { retry_after: None }.to_string(),
+        "Rate limit exceeded for the API"
+    );
+    assert_eq!(
+        ApiError::RateLimitExceeded {
+            retry_after: Some(Duration::from_secs(12))
+        }
+        .to_string(),
+        "Rate limit exceeded for the API, retry after 12s"
+    );
+}
+
+#[test]
+fn test_api_error_display() {
+    let err = UrlShortenerError::Api(ApiError::RateLimitExceeded {
+        retry_after: Some(Duration::from_secs(12)),
+    });
+    assert_eq!(
+        err.to_string(),
+        "API error: Rate limit exceeded for the API, retry after 12s"
+    );
+    assert_eq!(
+        UrlShortenerError::Api(ApiError::PasswordError).to_string(),
+        "API error: Incorrect password provided"
+    );
+    assert_eq!(
+        UrlShortenerError::Api(ApiError::Other("db busy".to_string())).to_string(),
+        "API error: db busy"
+    );
+}
+
+#[test]
+fn test_unexpected_display() {
+    let err = UrlShortenerError::Unexpected {
+        status: 503,
+        body: "Service Unavailable".to_string(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "Unexpected response with status 503: Service Unavailable"
+    );
+}
+
+#[test]
+fn test_max_clicks_display() {
+    use spoo_me::errors::ValidationError;
+
+    assert_eq!(
+        ValidationError::InvalidMaxClicks(0).to_string(),
+        "Max-clicks must be a positive integer, got: 0"
+    );
+    assert_eq!(
+        ValidationError::MaxClicksAboveLimit {
+            max_clicks: 101,
+            limit: 100
+        }
+        .to_string(),
+        "Max-clicks must be at most 100, got: 101"
+    );
+}
+
+#[test]
+fn test_error_source() {
+    use std::error::Error;
+
+    let http_err = reqwest::Client::new()
+        .get("not a url")
+        .build()
+        .expect_err("Expected an invalid URL error");
+    let err = UrlShortenerError::from(http_err);
+    let source = err.source().expect("Expected a source error");
+    assert!(source.downcast_ref::<reqwest::Error>().is_some());
+
+    let err = UrlShortenerError::Api(ApiError::UrlError);
+    let source = err.source().expect("Expected a source error");
+    assert!(matches!(
+        source.downcast_ref::<ApiError>(),
+        Some(ApiError::UrlError)
+    ));
+
+    assert!(UrlShortenerError::Other("oops".to_string())
+        .source()
+        .is_none());
+}
+
+#[test]
+fn test_from_conversions() {
+    use spoo_me::errors::ValidationError;
+
+    fn validate(max_clicks: u32) -> Result<(), UrlShortenerError> {
+        if max_clicks == 0 {
+            Err(ValidationError::InvalidMaxClicks(max_clicks))?;
+        }
+        Ok(())
+    }
+
+    assert!(matches!(
+        validate(0),
+        Err(UrlShortenerError::Validation(
+            ValidationError::InvalidMaxClicks(0)
+        ))
+    ));
+    assert!(matches!(
+        UrlShortenerError::from(ApiError::AliasError),
+        UrlShortenerError::Api(ApiError::AliasError)
+    ));
+
+    let json_err = serde_json::from_str::<u32>("nope").expect_err("Expected a JSON error");
+    assert!(matches!(
+        UrlShortenerError::from(json_err),
+        UrlShortenerError::Json(_)
+    ));
+}
+
+#[test]
+fn test_http_error_kinds() {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    // Nothing listens on the port of a dropped listener.
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let err = UrlShortenerError::from(
+        client
+            .get(format!("http://127.0.0.1:{}", port))
+            .send()
+            .unwrap_err(),
+    );
+    assert!(err.is_connect());
+    assert!(!err.is_timeout());
+
+    // A listener that never answers.
+    let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+    let err = UrlShortenerError::from(
+        client
+            .get(format!("http://{}", silent.local_addr().unwrap()))
+            .send()
+            .unwrap_err(),
+    );
+    assert!(err.is_timeout());
+    assert!(!err.is_connect());
+
+    // A listener that answers with a body that isn't JSON.
+    let server = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = server.accept().unwrap();
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request).unwrap();
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nnot")
+            .unwrap();
+    });
+    let err = UrlShortenerError::from(
+        client
+            .get(format!("http://{}", addr))
+            .send()
+            .unwrap()
+            .json::<serde_json::Value>()
+            .unwrap_err(),
+    );
+    handle.join().unwrap();
+    assert!(err.is_decode());
+    assert!(!err.is_timeout());
+
+    let err = UrlShortenerError::Other("not an HTTP error".to_string());
+    assert!(!err.is_timeout() && !err.is_connect() && !err.is_decode());
+}
@@ -0,0 +1,186 @@
+use spoo_me::{
+    errors::UrlShortenerError,
+    requests::{ExportFormat, ExportRequest, ExportResponse},
+};
+
+#[test]
+fn test_as_stats() {
+    let json = r#"{
+        "short_code": "ga",
+        "url": "https://google.com",
+        "total-clicks": 12,
+        "total_unique_clicks": 7,
+        "browser": {"Chrome": 8, "Firefox": 4}
+    }"#;
+
+    let stats = ExportResponse::from(json.as_bytes().to_vec())
+        .as_stats()
+        .expect("Failed to parse JSON export");
+
+    assert_eq!(stats.short_code, "ga");
+    assert_eq!(stats.total_clicks, 12);
+    assert_eq!(stats.browser.unwrap().get("Firefox"), Some(&4));
+}
+
+#[test]
+fn test_suggested_filename_without_format() {
+    let export = ExportResponse::from(b"data".to_vec());
+    assert!(export.format().is_none());
+    assert_eq!(export.suggested_filename("🔥"), "_1f525_.bin");
+}
+
+#[test]
+fn test_len() {
+    let export = ExportResponse::from(b"data".to_vec());
+    assert_eq!(export.len(), 4);
+    assert!(!export.is_empty());
+    assert_eq!(export.as_ref(), b"data");
+
+    let empty = ExportResponse::from(Vec::new());
+    assert_eq!(empty.len(), 0);
+    assert!(empty.is_empty());
+    assert!(AsRef::<[u8]>::as_ref(&empty).is_empty());
+}
+
+#[test]
+fn test_save_to_file() {
+    let path = std::env::temp_dir().join(format!("spoo-me-save-{}.json", std::process::id()));
+    ExportResponse::from(br#"{"short_code": "ga"}"#.to_vec())
+        .save_to_file(&path)
+        .expect("Failed to save export");
+
+    let saved = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(saved, br#"{"short_code": "ga"}"#);
+}
+
+#[test]
+fn test_as_json() {
+    let export = ExportResponse::from(br#"{"short_code": "ga", "total-clicks": 12}"#.to_vec());
+    let value: serde_json::Value = export.as_json().expect("Failed to parse JSON export");
+    assert_eq!(value["total-clicks"], 12);
+
+    let result = ExportResponse::from(b"<stats></stats>".to_vec()).as_stats();
+    assert!(
+        matches!(result, Err(UrlShortenerError::Json(_))),
+        "Expected a JSON error, got: {:?}",
+        result
+    );
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn test_as_stats_from_xml() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <stats>
+            <short_code>ga</short_code>
+            <url>https://google.com</url>
+            <total-clicks>12</total-clicks>
+            <total_unique_clicks>7</total_unique_clicks>
+            <creation-date>2024-01-01</creation-date>
+            <browser>
+                <Chrome>8</Chrome>
+                <Firefox>4</Firefox>
+            </browser>
+        </stats>"#;
+
+    let stats = ExportResponse::from(xml.as_bytes().to_vec())
+        .as_stats_from_xml()
+        .expect("Failed to parse XML export");
+
+    assert_eq!(stats.short_code, "ga");
+    assert_eq!(stats.url, "https://google.com");
+    assert_eq!(stats.total_clicks, 12);
+    assert_eq!(stats.total_unique_clicks, 7);
+    assert_eq!(stats.creation_date.as_deref(), Some("2024-01-01"));
+    assert_eq!(stats.browser.unwrap().get("Chrome"), Some(&8));
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn test_as_stats_from_xml_rejects_non_xml() {
+    let result = ExportResponse::from(br#"{"short_code": "ga"}"#.to_vec()).as_stats_from_xml();
+    assert!(
+        matches!(result, Err(UrlShortenerError::Xml(_))),
+        "Expected an XML error, got: {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_export_format_from_str() {
+    assert_eq!("json".parse(), Ok(ExportFormat::JSON));
+    assert_eq!("CSV".parse(), Ok(ExportFormat::CSV));
+    assert_eq!("Xlsx".parse(), Ok(ExportFormat::XLSX));
+    assert_eq!(" xml ".parse(), Ok(ExportFormat::XML));
+
+    let err = "pdf".parse::<ExportFormat>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unknown export format 'pdf', expected json, csv, xlsx or xml"
+    );
+    assert!("".parse::<ExportFormat>().is_err());
+}
+
+#[test]
+fn test_export_format_round_trip() {
+    for format in ExportFormat::all() {
+        let json = serde_json::to_string(&format).expect("Failed to serialize format");
+        let parsed: ExportFormat = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(parsed, format);
+        assert_eq!(format.to_string().parse(), Ok(format));
+    }
+}
+
+#[test]
+fn test_export_format_all() {
+    const ALL: [ExportFormat; 4] = ExportFormat::all();
+    assert_eq!(
+        ALL,
+        [
+            ExportFormat::JSON,
+            ExportFormat::CSV,
+            ExportFormat::XLSX,
+            ExportFormat::XML
+        ]
+    );
+}
+
+#[cfg(feature = "export-parse")]
+#[test]
+fn test_parse_csv() {
+    let export = ExportResponse::from(include_bytes!("fixtures/export.zip").to_vec());
+    let files = export.parse_csv().expect("Failed to parse CSV export");
+
+    assert_eq!(files.len(), 2);
+    let browsers = &files["browser.csv"];
+    assert_eq!(browsers.len(), 3);
+    assert_eq!(&browsers[0], vec!["Browser", "Count"]);
+    assert_eq!(&browsers[2], vec!["Firefox", "4"]);
+    assert_eq!(&files["country.csv"][1], vec!["India", "10"]);
+}
+
+#[cfg(feature = "export-parse")]
+#[test]
+fn test_parse_single_csv() {
+    let export = ExportResponse::from(b"Browser,Count\nChrome,8\n".to_vec());
+    let files = export.parse_csv().expect("Failed to parse CSV export");
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(&files["export.csv"][1], vec!["Chrome", "8"]);
+}
+
+#[cfg(feature = "export-parse")]
+#[test]
+fn test_parse_csv_invalid_zip() {
+    let export = ExportResponse::from(b"PK not really a zip".to_vec());
+    assert!(matches!(export.parse_csv(), Err(UrlShortenerError::Zip(_))));
+}
+
+#[test]
+fn test_export_request_with_password() {
+    assert_eq!(
+        ExportRequest::with_password("ga", ExportFormat::CSV, "Valid@123"),
+        ExportRequest::new("ga", ExportFormat::CSV).password("Valid@123")
+    );
+}
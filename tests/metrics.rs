@@ -0,0 +1,59 @@
+#[cfg(all(feature = "metrics", feature = "custom_url", not(feature = "blocking")))]
+#[cfg(test)]
+mod metrics_tests {
+    use metrics_util::{
+        debugging::{DebugValue, DebuggingRecorder},
+        MetricKind,
+    };
+    use spoo_me::{client::UrlShortenerClient, requests::StatsRequest};
+
+    #[test]
+    fn test_request_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        // The recorder is local to this thread, so the request runs on a current-thread runtime.
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create();
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(client.stats(StatsRequest::new("ga").password("Secret@123")))
+        });
+
+        mock.assert();
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let (key, _, _, value) = snapshot
+            .iter()
+            .find(|(key, ..)| {
+                key.kind() == MetricKind::Counter && key.key().name() == "spoo_me_requests_total"
+            })
+            .expect("request counter wasn't recorded");
+        assert_eq!(*value, DebugValue::Counter(1));
+
+        let labels: Vec<_> = key
+            .key()
+            .labels()
+            .map(|label| (label.key(), label.value()))
+            .collect();
+        assert_eq!(labels, [("endpoint", "stats"), ("outcome", "success")]);
+
+        assert!(snapshot.iter().any(|(key, ..)| {
+            key.kind() == MetricKind::Histogram
+                && key.key().name() == "spoo_me_request_duration_seconds"
+        }));
+    }
+}
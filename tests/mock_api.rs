@@ -0,0 +1,2031 @@
+#[cfg(all(feature = "custom_url", not(feature = "blocking")))]
+#[cfg(test)]
+mod async_mock_tests {
+    use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE};
+    use spoo_me::{
+        client::{UrlShortenerClient, MAX_REDIRECTS},
+        errors::{ApiError, PasswordViolation, UrlShortenerError, ValidationError},
+        requests::{
+            DeleteRequest, EmojiRequest, ExportFormat, ExportRequest, ResolveRequest,
+            ResolveResponse, ShortenRequest, StatsRequest,
+        },
+        retry::RetryPolicy,
+    };
+    use std::time::Duration;
+
+    fn fast_retries(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limit_by_default() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/emoji")
+            .with_status(429)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let client =
+            UrlShortenerClient::new_with_base_url(server.url()).with_retry_policy(fast_retries(2));
+        let response = client.emoji(EmojiRequest::new("https://example.com")).await;
+
+        mock.assert_async().await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { .. }))
+            ),
+            "Expected rate limit error, got: {:?}",
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retries_custom_api_error() {
+        let mut server = mockito::Server::new_async().await;
+        let busy = server
+            .mock("POST", "/emoji")
+            .with_status(503)
+            .with_body(r#"{"error": "db busy"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url())
+            .with_retry_policy(fast_retries(1))
+            .with_api_retry(|err| matches!(err, ApiError::Other(msg) if msg == "db busy"));
+        let response = client.emoji(EmojiRequest::new("https://example.com")).await;
+
+        busy.assert_async().await;
+        assert!(response.is_err(), "Expected the retried request to fail");
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        // Nothing listens on the port until the server below starts, so the first attempt fails
+        // to connect and only the retry reaches the server.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let client = UrlShortenerClient::new_with_base_url(format!("http://127.0.0.1:{port}"))
+            .with_retry_policy(RetryPolicy {
+                max_transient_retries: 1,
+                base_delay: Duration::from_millis(500),
+                jitter: false,
+                ..Default::default()
+            });
+
+        let start_server = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let mut server = mockito::Server::new_with_opts_async(mockito::ServerOpts {
+                port,
+                ..Default::default()
+            })
+            .await;
+            let mock = server
+                .mock("POST", "/")
+                .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+                .expect(1)
+                .create_async()
+                .await;
+            (server, mock)
+        };
+        let (response, (_server, mock)) = tokio::join!(
+            client.shorten(ShortenRequest::new("https://example.com")),
+            start_server
+        );
+
+        mock.assert_async().await;
+        assert_eq!(response.unwrap().short_url, "https://spoo.me/abc");
+    }
+
+    #[tokio::test]
+    async fn test_no_transient_retry_for_other_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let invalid = server
+            .mock("POST", "/")
+            .with_body("not json")
+            .expect(1)
+            .create_async()
+            .await;
+        let rejected = server
+            .mock("POST", "/emoji")
+            .with_status(400)
+            .with_body(r#"{"error": "UrlError"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client =
+            UrlShortenerClient::new_with_base_url(server.url()).with_retry_policy(RetryPolicy {
+                max_transient_retries: 3,
+                base_delay: Duration::from_millis(1),
+                ..Default::default()
+            });
+        let shortened = client
+            .shorten(ShortenRequest::new("https://example.com"))
+            .await;
+        let emoji = client.emoji(EmojiRequest::new("https://example.com")).await;
+
+        invalid.assert_async().await;
+        rejected.assert_async().await;
+        assert!(matches!(shortened, Err(UrlShortenerError::Json(_))));
+        assert!(matches!(
+            emoji,
+            Err(UrlShortenerError::Api(ApiError::UrlError))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_for_other_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/emoji")
+            .with_status(400)
+            .with_body(r#"{"error": "UrlError"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client =
+            UrlShortenerClient::new_with_base_url(server.url()).with_retry_policy(fast_retries(3));
+        let response = client.emoji(EmojiRequest::new("https://example.com")).await;
+
+        mock.assert_async().await;
+        assert!(
+            matches!(response, Err(UrlShortenerError::Api(ApiError::UrlError))),
+            "Expected URL error, got: {:?}",
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_without_budget() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/emoji")
+            .with_status(429)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.emoji(EmojiRequest::new("https://example.com")).await;
+
+        mock.assert_async().await;
+        assert!(response.is_err(), "Expected rate limit error");
+    }
+
+    #[tokio::test]
+    async fn test_verify_redirect() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/abc")
+            .with_status(302)
+            .with_header("Location", "https://example.com/target")
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/missing")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let short_url = format!("{}/abc", server.url());
+
+        assert!(client
+            .verify_redirect(&short_url, "https://example.com/target")
+            .await
+            .unwrap());
+        assert!(!client
+            .verify_redirect(&short_url, "https://example.com/other")
+            .await
+            .unwrap());
+        assert!(!client
+            .verify_redirect(
+                &format!("{}/missing", server.url()),
+                "https://example.com/target"
+            )
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_redirect_retries_rate_limit() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/abc")
+            .with_status(429)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client =
+            UrlShortenerClient::new_with_base_url(server.url()).with_retry_policy(fast_retries(1));
+        let response = client
+            .verify_redirect(
+                &format!("{}/abc", server.url()),
+                "https://example.com/target",
+            )
+            .await;
+
+        mock.assert_async().await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { .. }))
+            ),
+            "Expected rate limit error, got: {:?}",
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_redirect_headers_stay_on_base_url() {
+        let mut server = mockito::Server::new_async().await;
+        let own = server
+            .mock("GET", "/abc")
+            .match_header("x-app", "my-app")
+            .with_status(302)
+            .with_header("Location", "https://example.com/target")
+            .create_async()
+            .await;
+        let mut other_server = mockito::Server::new_async().await;
+        let other = other_server
+            .mock("GET", "/abc")
+            .match_header("x-app", mockito::Matcher::Missing)
+            .with_status(302)
+            .with_header("Location", "https://example.com/target")
+            .create_async()
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-app", HeaderValue::from_static("my-app"));
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .default_headers(headers)
+            .build();
+        for short_url in [
+            format!("{}/abc", server.url()),
+            format!("{}/abc", other_server.url()),
+        ] {
+            assert!(client
+                .verify_redirect(&short_url, "https://example.com/target")
+                .await
+                .unwrap());
+        }
+
+        own.assert_async().await;
+        other.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_correlation_id_header() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/stats/ga")
+            .match_header("X-Correlation-Id", "trace-123")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .stats(StatsRequest::new("ga").correlation_id("trace-123"))
+            .await;
+
+        mock.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+    }
+
+    #[cfg(feature = "url")]
+    #[tokio::test]
+    async fn test_url_normalization() {
+        use spoo_me::{requests::ShortenRequest, utils::NormalizeOptions};
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "url".into(),
+                "https://example.com/a/b".into(),
+            ))
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com/a/b"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url()).with_url_normalization(
+            NormalizeOptions {
+                strip_utm_params: true,
+                ..Default::default()
+            },
+        );
+        let response = client
+            .shorten(ShortenRequest::new(
+                "https://Example.COM//a/b?utm_source=news",
+            ))
+            .await;
+
+        mock.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to shorten URL: {:?}",
+            response.err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        // Non-routable address, so the connection attempt hangs until the timeout.
+        let mut client = UrlShortenerClient::new_with_base_url("http://10.255.255.1");
+        client.set_timeout(Duration::from_millis(500));
+
+        let started = std::time::Instant::now();
+        let response = client.stats(StatsRequest::new("ga")).await;
+
+        assert!(
+            matches!(response, Err(UrlShortenerError::Http(_))),
+            "Expected an HTTP error, got: {:?}",
+            response
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_with_client() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/stats/ga")
+            .match_header("X-App", "my-app")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("X-App", "my-app".parse().unwrap());
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let mut client = UrlShortenerClient::with_client(http);
+        client.set_base_url(server.url());
+        let response = client.stats(StatsRequest::new("ga")).await;
+
+        mock.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builder() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/emoji")
+            .match_header("User-Agent", "my-app/1.0")
+            .with_status(429)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .user_agent("my-app/1.0")
+            .retry_policy(fast_retries(1))
+            .build();
+        let response = client.emoji(EmojiRequest::new("https://example.com")).await;
+
+        mock.assert_async().await;
+        assert!(response.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_user_agent() {
+        let mut server = mockito::Server::new_async().await;
+        let default_mock = server
+            .mock("POST", "/stats/ga")
+            .match_header("User-Agent", spoo_me::client::DEFAULT_USER_AGENT)
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+
+        let mut client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats(StatsRequest::new("ga")).await;
+        default_mock.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+
+        let custom_mock = server
+            .mock("POST", "/stats/ga")
+            .match_header("User-Agent", "my-app/1.0")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+
+        client.set_user_agent("my-app/1.0");
+        let response = client.stats(StatsRequest::new("ga")).await;
+        custom_mock.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_emoji_not_sent() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("POST", "/emoji").expect(0).create_async().await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .emoji(EmojiRequest::new("https://example.com").emojies("abc"))
+            .await;
+
+        mock.assert_async().await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(
+                    ValidationError::InvalidEmojiSequence(ref seq)
+                )) if seq == "abc"
+            ),
+            "Expected invalid emoji error, got: {:?}",
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emoji_validation() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("POST", "/emoji").expect(0).create_async().await;
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        let response = client
+            .emoji(EmojiRequest::new("https://example.com").password("short"))
+            .await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(
+                    ValidationError::InvalidPasswordFormat(PasswordViolation::TooShort)
+                ))
+            ),
+            "Expected invalid password error, got: {:?}",
+            response
+        );
+
+        let response = client.emoji(EmojiRequest::new("not a url")).await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(
+                    ValidationError::InvalidUrlFormat(_)
+                ))
+            ),
+            "Expected invalid URL error, got: {:?}",
+            response
+        );
+
+        let response = client
+            .emoji(EmojiRequest::new("https://example.com").max_clicks(0))
+            .await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(
+                    ValidationError::InvalidMaxClicks(0)
+                ))
+            ),
+            "Expected invalid max-clicks error, got: {:?}",
+            response
+        );
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_emoji_unexpected_error() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/emoji")
+            .with_status(500)
+            .with_body("oops")
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .emoji(EmojiRequest::new("https://example.com").emojies("🔥"))
+            .await;
+
+        mock.assert_async().await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Unexpected { status: 500, ref body }) if body == "oops"
+            ),
+            "Expected unexpected response error, got: {:?}",
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("DELETE", "/ga")
+            .match_body("password=Valid%40123")
+            .with_status(200)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("DELETE", "/gone")
+            .match_header("content-type", mockito::Matcher::Missing)
+            .match_body("")
+            .with_status(400)
+            .with_body(r#"{"error": "AliasError"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .delete(DeleteRequest::new("ga").password("Valid@123"))
+            .await;
+        mock.assert_async().await;
+        assert!(response.is_ok(), "Failed to delete: {:?}", response.err());
+
+        let response = client.delete(DeleteRequest::new("gone")).await;
+        missing.assert_async().await;
+        assert!(
+            matches!(response, Err(UrlShortenerError::Api(ApiError::AliasError))),
+            "Expected alias error, got: {:?}",
+            response
+        );
+
+        let response = client.delete(DeleteRequest::new("")).await;
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Validation(
+                ValidationError::InvalidAliasFormat(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_shorten_many() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for i in [1, 2, 4] {
+            mocks.push(
+                server
+                    .mock("POST", "/")
+                    .match_body(mockito::Matcher::UrlEncoded(
+                        "url".into(),
+                        format!("https://example.com/{}", i),
+                    ))
+                    .with_body(format!(
+                        r#"{{"short_url": "https://spoo.me/s{0}", "domain": "spoo.me", "original_url": "https://example.com/{0}"}}"#,
+                        i
+                    ))
+                    .create_async()
+                    .await,
+            );
+        }
+        mocks.push(
+            server
+                .mock("POST", "/")
+                .match_body(mockito::Matcher::UrlEncoded(
+                    "url".into(),
+                    "https://example.com/3".into(),
+                ))
+                .with_status(400)
+                .with_body(r#"{"error": "UrlError"}"#)
+                .create_async()
+                .await,
+        );
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let requests = (1..=4)
+            .map(|i| ShortenRequest::new(format!("https://example.com/{}", i)))
+            .collect();
+        let results = client.shorten_many(requests, 2).await;
+
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(results.len(), 4);
+        for (i, result) in results.iter().enumerate() {
+            if i == 2 {
+                assert!(
+                    matches!(result, Err(UrlShortenerError::Api(ApiError::UrlError))),
+                    "Expected URL error, got: {:?}",
+                    result
+                );
+            } else {
+                let response = result.as_ref().expect("Failed to shorten URL");
+                assert_eq!(response.short_url, format!("https://spoo.me/s{}", i + 1));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let password_page = r#"<form method="post"><input type="password" name="password"></form>"#;
+        let mut server = mockito::Server::new_async().await;
+        let open = server
+            .mock("GET", "/ga")
+            .with_status(302)
+            .with_header("Location", "https://google.com")
+            .create_async()
+            .await;
+        let locked = server
+            .mock("GET", "/secret")
+            .with_body(password_page)
+            .create_async()
+            .await;
+        let unlocked = server
+            .mock("POST", "/secret")
+            .match_body("password=Valid%40123")
+            .with_status(302)
+            .with_header("Location", "https://example.com")
+            .create_async()
+            .await;
+        let wrong_password = server
+            .mock("POST", "/secret")
+            .match_body("password=Wrong%40123")
+            .with_body(password_page)
+            .create_async()
+            .await;
+        let not_a_redirect = server
+            .mock("GET", "/page")
+            .with_body("<html>Welcome</html>")
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        let response = client.resolve(ResolveRequest::new("ga")).await.unwrap();
+        assert_eq!(
+            response,
+            ResolveResponse {
+                url: Some("https://google.com".to_string()),
+                password_protected: false,
+            }
+        );
+
+        let response = client.resolve(ResolveRequest::new("secret")).await.unwrap();
+        assert_eq!(
+            response,
+            ResolveResponse {
+                url: None,
+                password_protected: true,
+            }
+        );
+
+        let response = client
+            .resolve(ResolveRequest::new("secret").password("Valid@123"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            ResolveResponse {
+                url: Some("https://example.com".to_string()),
+                password_protected: false,
+            }
+        );
+
+        let response = client
+            .resolve(ResolveRequest::new("secret").password("Wrong@123"))
+            .await;
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Api(ApiError::PasswordError))
+        ));
+
+        let response = client.resolve(ResolveRequest::new("page")).await;
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Unexpected { status: 200, .. })
+        ));
+
+        open.assert_async().await;
+        locked.assert_async().await;
+        unlocked.assert_async().await;
+        wrong_password.assert_async().await;
+        not_a_redirect.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_export_to_writer() {
+        let body: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/export/ga/xlsx")
+            .with_body(&body)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        let mut buffer = Vec::new();
+        let written = client
+            .export_to_writer(ExportRequest::new("ga", ExportFormat::XLSX), &mut buffer)
+            .await
+            .expect("Failed to export to buffer");
+
+        let path = std::env::temp_dir().join(format!("spoo-me-export-{}.xlsx", std::process::id()));
+        let mut file = tokio::fs::File::create(&path).await.unwrap();
+        let file_written = client
+            .export_to_writer(ExportRequest::new("ga", ExportFormat::XLSX), &mut file)
+            .await
+            .expect("Failed to export to file");
+        let file_len = tokio::fs::metadata(&path).await.unwrap().len();
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(buffer, body);
+        assert_eq!(written, body.len() as u64);
+        assert_eq!(file_written, written);
+        assert_eq!(file_len, written);
+    }
+
+    #[tokio::test]
+    async fn test_export_suggested_filename() {
+        let mut server = mockito::Server::new_async().await;
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        let cases = [
+            (ExportFormat::JSON, "json", "ga.json"),
+            (ExportFormat::CSV, "csv", "ga.zip"),
+            (ExportFormat::XLSX, "xlsx", "ga.xlsx"),
+            (ExportFormat::XML, "xml", "ga.xml"),
+        ];
+        for (format, path, expected) in cases {
+            let mock = server
+                .mock("POST", format!("/export/ga/{}", path).as_str())
+                .with_body("data")
+                .create_async()
+                .await;
+
+            let export = client
+                .export(ExportRequest::new("ga", format))
+                .await
+                .expect("Failed to export");
+
+            mock.assert_async().await;
+            assert_eq!(export.suggested_filename("ga"), expected);
+        }
+
+        let mock = server
+            .mock("POST", "/export/ga/csv")
+            .with_header(
+                "Content-Type",
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            )
+            .with_body("data")
+            .create_async()
+            .await;
+        let export = client
+            .export(ExportRequest::new("ga", ExportFormat::CSV))
+            .await
+            .expect("Failed to export");
+
+        mock.assert_async().await;
+        assert_eq!(
+            export.content_type(),
+            Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        );
+        assert!(matches!(export.format(), Some(ExportFormat::CSV)));
+        assert_eq!(export.suggested_filename("ga"), "ga.xlsx");
+    }
+
+    #[tokio::test]
+    async fn test_stats_without_password_sends_no_body() {
+        let mut server = mockito::Server::new_async().await;
+        let without = server
+            .mock("POST", "/stats/ga")
+            .match_header("content-type", mockito::Matcher::Missing)
+            .match_body("")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+        let with = server
+            .mock("POST", "/stats/secret")
+            .match_header("content-type", "application/x-www-form-urlencoded")
+            .match_body("password=Valid%40123")
+            .with_body(r#"{"short_code": "secret", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats(StatsRequest::new("ga")).await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+        let response = client
+            .stats(StatsRequest::new("secret").password("Valid@123"))
+            .await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+
+        without.assert_async().await;
+        with.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_base_url_with_path_prefix() {
+        let mut server = mockito::Server::new_async().await;
+        let shorten = server
+            .mock("POST", "/spoo/")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let emoji = server
+            .mock("POST", "/spoo/emoji")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let stats = server
+            .mock("POST", "/spoo/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let export = server
+            .mock("POST", "/spoo/export/ga/json")
+            .with_body("{}")
+            .expect(2)
+            .create_async()
+            .await;
+
+        for base_url in [
+            format!("{}/spoo", server.url()),
+            format!("{}/spoo/", server.url()),
+        ] {
+            let client = UrlShortenerClient::new_with_base_url(base_url.as_str());
+            let response = client
+                .shorten(ShortenRequest::new("https://example.com"))
+                .await;
+            assert!(response.is_ok(), "{}: {:?}", base_url, response.err());
+            let response = client.emoji(EmojiRequest::new("https://example.com")).await;
+            assert!(response.is_ok(), "{}: {:?}", base_url, response.err());
+            let response = client.stats(StatsRequest::new("ga")).await;
+            assert!(response.is_ok(), "{}: {:?}", base_url, response.err());
+            let response = client
+                .export(ExportRequest::new("ga", ExportFormat::JSON))
+                .await;
+            assert!(response.is_ok(), "{}: {:?}", base_url, response.err());
+        }
+
+        shorten.assert_async().await;
+        emoji.assert_async().await;
+        stats.assert_async().await;
+        export.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_empty_short_code() {
+        let mut server = mockito::Server::new_async().await;
+        let any = server
+            .mock("POST", mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats(StatsRequest::new("")).await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(ValidationError::InvalidAliasFormat(ref code)))
+                    if code.is_empty()
+            ),
+            "Expected invalid alias error, got: {:?}",
+            response
+        );
+        let response = client
+            .export(ExportRequest::new("", ExportFormat::JSON))
+            .await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Validation(ValidationError::InvalidAliasFormat(ref code)))
+                    if code.is_empty()
+            ),
+            "Expected invalid alias error, got: {:?}",
+            response
+        );
+
+        any.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_stats_emoji_short_code() {
+        let mut server = mockito::Server::new_async().await;
+        let stats = server
+            .mock("POST", "/stats/%F0%9F%94%A5%F0%9F%8D%95")
+            .with_body(r#"{"short_code": "🔥🍕", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+        let export = server
+            .mock("POST", "/export/%F0%9F%94%A5%F0%9F%8D%95/json")
+            .with_body("{}")
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats(StatsRequest::new("🔥🍕")).await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+        assert_eq!(response.unwrap().short_code, "🔥🍕");
+        let response = client
+            .export(ExportRequest::new("🔥🍕", ExportFormat::JSON))
+            .await;
+        assert!(response.is_ok(), "Failed to export: {:?}", response.err());
+
+        stats.assert_async().await;
+        export.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_default_headers() {
+        let mut server = mockito::Server::new_async().await;
+        let shorten = server
+            .mock("POST", "/")
+            .match_header("x-debug", "1")
+            .match_header("accept", "text/html")
+            .match_header("content-type", "application/x-www-form-urlencoded")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-debug", HeaderValue::from_static("1"));
+        headers.insert(ACCEPT, HeaderValue::from_static("text/html"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .default_headers(headers)
+            .build();
+        let response = client
+            .shorten(ShortenRequest::new("https://example.com"))
+            .await;
+
+        shorten.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to shorten URL: {:?}",
+            response.err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_header_provider() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for request_id in ["1", "2"] {
+            let mock = server
+                .mock("POST", "/stats/ga")
+                .match_header("x-request-id", request_id)
+                .match_header("accept", "application/json")
+                .match_header("content-type", mockito::Matcher::Missing)
+                .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+                .expect(1)
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+
+        let calls = AtomicU64::new(0);
+        let client =
+            UrlShortenerClient::new_with_base_url(server.url()).with_header_provider(move || {
+                let request_id = calls.fetch_add(1, Ordering::Relaxed) + 1;
+                let mut headers = HeaderMap::new();
+                headers.insert("x-request-id", HeaderValue::from(request_id));
+                headers.insert(ACCEPT, HeaderValue::from_static("text/html"));
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                headers
+            });
+        for _ in 0..2 {
+            let response = client.stats(StatsRequest::new("ga")).await;
+            assert!(
+                response.is_ok(),
+                "Failed to get stats: {:?}",
+                response.err()
+            );
+        }
+
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_all() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for format in ["json", "csv", "xlsx"] {
+            mocks.push(
+                server
+                    .mock("POST", format!("/export/ga/{}", format).as_str())
+                    .match_body("password=Valid%40123")
+                    .with_body(format)
+                    .create_async()
+                    .await,
+            );
+        }
+        mocks.push(
+            server
+                .mock("POST", "/export/ga/xml")
+                .with_status(500)
+                .with_body(r#"{"error": "export failed"}"#)
+                .create_async()
+                .await,
+        );
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let results = client.export_all("ga", Some("Valid@123")).await;
+
+        for mock in &mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(results.len(), 4);
+        for format in [ExportFormat::JSON, ExportFormat::CSV, ExportFormat::XLSX] {
+            let export = results[&format].as_ref().expect("Export failed");
+            assert_eq!(export.data(), format.to_string().as_bytes());
+            assert_eq!(export.format(), Some(&format));
+        }
+        assert!(results[&ExportFormat::XML].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shorten_then_stats() {
+        let mut server = mockito::Server::new_async().await;
+        let shorten = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "password".into(),
+                "Valid@123".into(),
+            ))
+            .with_body(r#"{"short_url": "https://spoo.me/fresh", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+        let not_yet = server
+            .mock("POST", "/stats/fresh")
+            .with_status(404)
+            .with_body(r#"{"error": "UrlError"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let stats = server
+            .mock("POST", "/stats/fresh")
+            .match_body("password=Valid%40123")
+            .with_body(r#"{"short_code": "fresh", "url": "https://example.com", "total-clicks": 0, "total_unique_clicks": 0}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .shorten_then_stats(ShortenRequest::new("https://example.com").password("Valid@123"))
+            .await;
+
+        shorten.assert_async().await;
+        not_yet.assert_async().await;
+        stats.assert_async().await;
+        let (shortened, stats) = response.expect("Failed to shorten then get stats");
+        assert_eq!(shortened.short_url, "https://spoo.me/fresh");
+        assert_eq!(stats.short_code, "fresh");
+    }
+
+    #[tokio::test]
+    async fn test_shorten_then_stats_no_retry_for_other_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/")
+            .with_body(r#"{"short_url": "https://spoo.me/fresh", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+        let rejected = server
+            .mock("POST", "/stats/fresh")
+            .with_status(401)
+            .with_body(r#"{"error": "PasswordError"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .shorten_then_stats(ShortenRequest::new("https://example.com"))
+            .await;
+
+        rejected.assert_async().await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Api(ApiError::PasswordError))
+            ),
+            "Expected a password error, got: {:?}",
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shorten_then_stats_skips_cache() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/")
+            .with_body(r#"{"short_url": "https://spoo.me/fresh", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+        let stats = server
+            .mock("POST", "/stats/fresh")
+            .with_body(r#"{"short_code": "fresh", "url": "https://example.com", "total-clicks": 0, "total_unique_clicks": 0}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url())
+            .with_stats_cache(Duration::from_secs(60));
+        client.stats(StatsRequest::new("fresh")).await.unwrap();
+        let response = client
+            .shorten_then_stats(ShortenRequest::new("https://example.com"))
+            .await;
+
+        stats.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to shorten then get stats: {:?}",
+            response.err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stats_cache() {
+        let mut server = mockito::Server::new_async().await;
+        let stats = server
+            .mock("POST", "/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .stats_cache(Duration::from_secs(60))
+            .build();
+        let shared = client.clone();
+        let first = client.stats(StatsRequest::new("ga")).await;
+        assert!(first.is_ok(), "Failed to get stats: {:?}", first.err());
+        let second = shared.stats(StatsRequest::new("ga")).await;
+        assert_eq!(second.ok(), first.ok());
+
+        client.clear_cache();
+        let third = client.stats(StatsRequest::new("ga")).await;
+        assert!(third.is_ok(), "Failed to get stats: {:?}", third.err());
+
+        stats.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_stats_cache_needs_password() {
+        let mut server = mockito::Server::new_async().await;
+        let authorized = server
+            .mock("POST", "/stats/secret")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "password".into(),
+                "Right@123".into(),
+            ))
+            .with_body(r#"{"short_code": "secret", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let rejected = server
+            .mock("POST", "/stats/secret")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "password".into(),
+                "Wrong@123".into(),
+            ))
+            .with_status(401)
+            .with_body(r#"{"error": "PasswordError"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url())
+            .with_stats_cache(Duration::from_secs(60));
+        let first = client
+            .stats(StatsRequest::new("secret").password("Right@123"))
+            .await;
+        assert!(first.is_ok(), "Failed to get stats: {:?}", first.err());
+        let second = client
+            .stats(StatsRequest::new("secret").password("Wrong@123"))
+            .await;
+
+        authorized.assert_async().await;
+        rejected.assert_async().await;
+        assert!(
+            matches!(second, Err(UrlShortenerError::Api(ApiError::PasswordError))),
+            "Expected a password error, got: {:?}",
+            second
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stats_cache_expires() {
+        let mut server = mockito::Server::new_async().await;
+        let stats = server
+            .mock("POST", "/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url())
+            .with_stats_cache(Duration::from_millis(10));
+        let first = client.stats(StatsRequest::new("ga")).await;
+        assert!(first.is_ok(), "Failed to get stats: {:?}", first.err());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = client.stats(StatsRequest::new("ga")).await;
+        assert!(second.is_ok(), "Failed to get stats: {:?}", second.err());
+
+        stats.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_html_error_body() {
+        let html = format!(
+            "<html><head><title>502 Bad Gateway</title></head><body>{}</body></html>",
+            "<p>upstream unavailable</p>".repeat(20)
+        );
+        let mut server = mockito::Server::new_async().await;
+        let bad_gateway = server
+            .mock("POST", "/stats/ga")
+            .with_status(502)
+            .with_header("content-type", "text/html")
+            .with_body(&html)
+            .create_async()
+            .await;
+        let empty = server
+            .mock("POST", "/stats/empty")
+            .with_status(502)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats(StatsRequest::new("ga")).await;
+        match response {
+            Err(UrlShortenerError::Unexpected { status, body }) => {
+                assert_eq!(status, 502);
+                assert_eq!(body.chars().count(), 201);
+                assert!(body.starts_with("<html><head><title>502 Bad Gateway"));
+                assert!(body.ends_with('…'));
+            }
+            other => panic!("Expected unexpected response error, got: {:?}", other),
+        }
+
+        let response = client.stats(StatsRequest::new("empty")).await;
+        assert!(
+            matches!(
+                response,
+                Err(UrlShortenerError::Unexpected { status: 502, ref body }) if body.is_empty()
+            ),
+            "Expected unexpected response error, got: {:?}",
+            response
+        );
+
+        bad_gateway.assert_async().await;
+        empty.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_max_clicks_limit() {
+        let mut server = mockito::Server::new_async().await;
+        let shorten = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "max-clicks".into(),
+                "100".into(),
+            ))
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let emoji = server.mock("POST", "/emoji").expect(0).create_async().await;
+
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .max_clicks_limit(100)
+            .build();
+        let response = client
+            .shorten(ShortenRequest::new("https://example.com").max_clicks(100))
+            .await;
+        assert!(
+            response.is_ok(),
+            "Failed to shorten URL: {:?}",
+            response.err()
+        );
+        let response = client
+            .shorten(ShortenRequest::new("https://example.com").max_clicks(101))
+            .await;
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Validation(
+                ValidationError::MaxClicksAboveLimit {
+                    max_clicks: 101,
+                    limit: 100
+                }
+            ))
+        ));
+        let response = client
+            .emoji(EmojiRequest::new("https://example.com").max_clicks(u32::MAX))
+            .await;
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Validation(
+                ValidationError::MaxClicksAboveLimit {
+                    max_clicks: u32::MAX,
+                    limit: 100
+                }
+            ))
+        ));
+
+        shorten.assert_async().await;
+        emoji.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let mut server = mockito::Server::new_async().await;
+        let slow = server
+            .mock("POST", "/export/ga/xlsx")
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_millis(500));
+                w.write_all(b"data")
+            })
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .timeout(Duration::from_secs(30))
+            .build();
+        let started = std::time::Instant::now();
+        let response = client
+            .export(ExportRequest::new("ga", ExportFormat::XLSX).timeout(Duration::from_millis(50)))
+            .await;
+
+        slow.assert_async().await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(
+            matches!(response, Err(UrlShortenerError::Http(ref err)) if err.is_timeout()),
+            "Expected timeout error, got: {:?}",
+            response
+        );
+    }
+
+    #[cfg(feature = "middleware")]
+    struct DebugHeader;
+
+    #[cfg(feature = "middleware")]
+    impl reqwest_middleware::RequestInitialiser for DebugHeader {
+        fn init(
+            &self,
+            req: reqwest_middleware::RequestBuilder,
+        ) -> reqwest_middleware::RequestBuilder {
+            req.header("x-debug", "1")
+        }
+    }
+
+    #[cfg(feature = "middleware")]
+    struct Refuse;
+
+    #[cfg(feature = "middleware")]
+    #[async_trait::async_trait]
+    impl reqwest_middleware::Middleware for Refuse {
+        async fn handle(
+            &self,
+            _req: reqwest::Request,
+            _extensions: &mut http::Extensions,
+            _next: reqwest_middleware::Next<'_>,
+        ) -> reqwest_middleware::Result<reqwest::Response> {
+            Err(reqwest_middleware::Error::middleware(
+                std::io::Error::other("refused"),
+            ))
+        }
+    }
+
+    #[cfg(feature = "middleware")]
+    #[tokio::test]
+    async fn test_middleware_client() {
+        let mut server = mockito::Server::new_async().await;
+        let stats = server
+            .mock("POST", "/stats/ga")
+            .match_header("x-debug", "1")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 3, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with_init(DebugHeader)
+            .build();
+        let mut client = UrlShortenerClient::with_middleware_client(client);
+        client.set_base_url(server.url());
+        let response = client.stats(StatsRequest::new("ga")).await;
+
+        stats.assert_async().await;
+        assert_eq!(response.unwrap().total_clicks, 3);
+    }
+
+    #[cfg(feature = "middleware")]
+    #[tokio::test]
+    async fn test_middleware_error() {
+        let mut server = mockito::Server::new_async().await;
+        let stats = server
+            .mock("POST", "/stats/ga")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with(Refuse)
+            .build();
+        let mut client = UrlShortenerClient::with_middleware_client(client);
+        client.set_base_url(server.url());
+        let response = client.stats(StatsRequest::new("ga")).await;
+
+        stats.assert_async().await;
+        assert!(matches!(response, Err(UrlShortenerError::Middleware(_))));
+    }
+
+    #[tokio::test]
+    async fn test_follow() {
+        let mut server = mockito::Server::new_async().await;
+        let short = server
+            .mock("GET", "/abc")
+            .with_status(302)
+            .with_header("location", &format!("{}/landing", server.url()))
+            .create_async()
+            .await;
+        let landing = server
+            .mock("GET", "/landing")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let result = client.follow("abc").await.unwrap();
+
+        short.assert_async().await;
+        landing.assert_async().await;
+        assert_eq!(
+            result.chain,
+            vec![
+                format!("{}/abc", server.url()),
+                format!("{}/landing", server.url())
+            ]
+        );
+        assert_eq!(result.final_url, format!("{}/landing", server.url()));
+        assert_eq!(result.status, 404);
+        assert!(!result.max_redirects_reached);
+    }
+
+    #[tokio::test]
+    async fn test_follow_retry_resets_chain() {
+        let mut server = mockito::Server::new_async().await;
+        let rate_limited = server
+            .mock("GET", "/abc")
+            .with_status(429)
+            .expect(1)
+            .create_async()
+            .await;
+        let short = server
+            .mock("GET", "/abc")
+            .with_status(302)
+            .with_header("location", &format!("{}/landing", server.url()))
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/landing")
+            .with_body("landed")
+            .create_async()
+            .await;
+
+        let client =
+            UrlShortenerClient::new_with_base_url(server.url()).with_retry_policy(fast_retries(1));
+        let result = client.follow("abc").await.unwrap();
+
+        rate_limited.assert_async().await;
+        short.assert_async().await;
+        assert_eq!(
+            result.chain,
+            vec![
+                format!("{}/abc", server.url()),
+                format!("{}/landing", server.url())
+            ]
+        );
+        assert_eq!(result.status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_follow_headers_stay_on_base_url() {
+        let mut server = mockito::Server::new_async().await;
+        let mut other_server = mockito::Server::new_async().await;
+        let short = server
+            .mock("GET", "/abc")
+            .match_header("x-app", "my-app")
+            .with_status(302)
+            .with_header("location", &format!("{}/landing", other_server.url()))
+            .create_async()
+            .await;
+        let landing = other_server
+            .mock("GET", "/landing")
+            .match_header("x-app", mockito::Matcher::Missing)
+            .with_body("landed")
+            .create_async()
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-app", HeaderValue::from_static("my-app"));
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .default_headers(headers)
+            .build();
+        let result = client.follow("abc").await.unwrap();
+
+        short.assert_async().await;
+        landing.assert_async().await;
+        assert_eq!(result.final_url, format!("{}/landing", other_server.url()));
+        assert_eq!(result.status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_follow_max_redirects() {
+        let mut server = mockito::Server::new_async().await;
+        let looping = server
+            .mock("GET", "/abc")
+            .with_status(302)
+            .with_header("location", &format!("{}/abc", server.url()))
+            .expect(MAX_REDIRECTS + 1)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let result = client.follow("abc").await.unwrap();
+
+        looping.assert_async().await;
+        assert_eq!(result.chain.len(), MAX_REDIRECTS + 1);
+        assert_eq!(result.status, 302);
+        assert!(result.max_redirects_reached);
+    }
+
+    #[tokio::test]
+    async fn test_follow_not_found() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/abc")
+            .with_status(404)
+            .with_body("<html>Not Found</html>")
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let result = client.follow("abc").await;
+
+        mock.assert_async().await;
+        assert!(matches!(
+            result,
+            Err(UrlShortenerError::Unexpected { status: 404, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_shorten_percent_encodes_url() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "url".into(),
+                "https://example.com/my%20file%C3%A9.pdf".into(),
+            ))
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com/my%20file%C3%A9.pdf"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .shorten(ShortenRequest::new("https://example.com/my fileé.pdf"))
+            .await;
+
+        mock.assert_async().await;
+        assert!(response
+            .unwrap()
+            .matches_input("https://example.com/my fileé.pdf"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_callback() {
+        use std::sync::{Arc, Mutex};
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/emoji")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(2)
+            .create_async()
+            .await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&seen);
+        let client = UrlShortenerClient::new_with_base_url(server.url())
+            .with_retry_policy(fast_retries(1))
+            .with_rate_limit_callback(move |retry_after| {
+                recorder.lock().unwrap().push(retry_after);
+            });
+        let response = client.emoji(EmojiRequest::new("https://example.com")).await;
+
+        mock.assert_async().await;
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { .. }))
+        ));
+        assert_eq!(*seen.lock().unwrap(), vec![Some(Duration::ZERO); 2]);
+    }
+
+    #[tokio::test]
+    async fn test_shorten_with_headers() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .with_header("x-ratelimit-remaining", "42")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let (response, headers) = client
+            .shorten_with_headers(ShortenRequest::new("https://example.com"))
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.short_url, "https://spoo.me/abc");
+        assert_eq!(headers["x-ratelimit-remaining"], "42");
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_header() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_header("idempotency-key", "key-123")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .shorten(ShortenRequest::new("https://example.com").idempotency_key("key-123"))
+            .await;
+
+        mock.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to shorten URL: {:?}",
+            response.err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_warmup() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("HEAD", "/")
+            .with_status(405)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.warmup().await;
+
+        mock.assert_async().await;
+        assert!(response.is_ok(), "Warmup failed: {:?}", response.err());
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let mut server = mockito::Server::new_async().await;
+        let healthy = server
+            .mock("GET", "/")
+            .with_body("<html>spoo.me</html>")
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.health().await;
+
+        healthy.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Health check failed: {:?}",
+            response.err()
+        );
+
+        healthy.remove_async().await;
+        let rate_limited = server
+            .mock("GET", "/")
+            .with_status(429)
+            .create_async()
+            .await;
+
+        let response = client.health().await;
+
+        rate_limited.assert_async().await;
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Api(ApiError::RateLimitExceeded { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_default_request_options() {
+        use mockito::Matcher;
+
+        let mut server = mockito::Server::new_async().await;
+        let defaulted = server
+            .mock("POST", "/")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("max-clicks".into(), "10".into()),
+                Matcher::UrlEncoded("block-bots".into(), "true".into()),
+            ]))
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+        let explicit = server
+            .mock("POST", "/emoji")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("max-clicks".into(), "5".into()),
+                Matcher::UrlEncoded("block-bots".into(), "false".into()),
+            ]))
+            .with_body(r#"{"short_url": "https://spoo.me/🔥", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .default_max_clicks(10)
+            .default_block_bots(true)
+            .build();
+        let shortened = client
+            .shorten(ShortenRequest::new("https://example.com"))
+            .await;
+        let emoji = client
+            .emoji(
+                EmojiRequest::new("https://example.com")
+                    .max_clicks(5)
+                    .block_bots(false),
+            )
+            .await;
+
+        defaulted.assert_async().await;
+        explicit.assert_async().await;
+        assert!(
+            shortened.is_ok(),
+            "Failed to shorten URL: {:?}",
+            shortened.err()
+        );
+        assert!(
+            emoji.is_ok(),
+            "Failed to create emoji URL: {:?}",
+            emoji.err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shorten_simple() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/")
+            .with_body(r#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/emoji")
+            .with_body(r#"{"short_url": "https://spoo.me/🔥", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+
+        assert_eq!(
+            client.shorten_simple("https://example.com").await.unwrap(),
+            "https://spoo.me/abc"
+        );
+        assert_eq!(
+            client.emoji_simple("https://example.com").await.unwrap(),
+            "https://spoo.me/🔥"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_self_hosted_short_url() {
+        let mut server = mockito::Server::new_async().await;
+        let body = format!(
+            r#"{{"short_url": "{0}/abc", "domain": "{1}", "original_url": "https://example.com"}}"#,
+            server.url(),
+            server.host_with_port()
+        );
+        server
+            .mock("POST", "/")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(format!("{}/", server.url()));
+        let response = client
+            .shorten(ShortenRequest::new("https://example.com"))
+            .await
+            .unwrap();
+
+        assert_eq!(client.base_url(), server.url());
+        assert!(response.is_on(client.base_url()));
+        assert!(!response.is_on("https://spoo.me"));
+    }
+
+    #[tokio::test]
+    async fn test_stats_raw() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/stats/ga")
+            .with_body(
+                r#"{"short_code": "ga", "url": "https://google.com", "clicks": {"total": 1}}"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client.stats(StatsRequest::new("ga")).await;
+        assert!(
+            matches!(response, Err(UrlShortenerError::Json(_))),
+            "Expected a JSON error, got: {:?}",
+            response
+        );
+
+        let raw = client.stats_raw(StatsRequest::new("ga")).await.unwrap();
+        assert_eq!(raw["short_code"], "ga");
+        assert_eq!(raw["clicks"]["total"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_requires_password() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/stats/secret")
+            .with_status(401)
+            .with_body(r#"{"error": "PasswordError"}"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/stats/missing")
+            .with_status(404)
+            .with_body(r#"{"error": "UrlError"}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        assert!(client.requires_password("secret").await.unwrap());
+        assert!(!client.requires_password("ga").await.unwrap());
+        let missing = client.requires_password("missing").await;
+        assert!(
+            matches!(missing, Err(UrlShortenerError::Api(ApiError::UrlError))),
+            "Expected a URL error, got: {:?}",
+            missing
+        );
+    }
+
+    #[cfg(feature = "cancellation")]
+    #[tokio::test]
+    async fn test_cancellation() {
+        use tokio_util::sync::CancellationToken;
+
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/")
+            .with_chunked_body(|writer| {
+                std::thread::sleep(Duration::from_secs(2));
+                writer.write_all(br#"{"short_url": "https://spoo.me/abc", "domain": "spoo.me", "original_url": "https://example.com"}"#)
+            })
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let token = CancellationToken::new();
+        let canceller = {
+            let token = token.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                token.cancel();
+            })
+        };
+        let started = std::time::Instant::now();
+        let response = client
+            .shorten_with_cancellation(ShortenRequest::new("https://example.com"), &token)
+            .await;
+        canceller.await.unwrap();
+
+        assert!(
+            matches!(response, Err(UrlShortenerError::Cancelled)),
+            "Expected a cancelled error, got: {:?}",
+            response
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let response = client
+            .stats_with_cancellation(StatsRequest::new("ga"), &token)
+            .await;
+        assert!(matches!(response, Err(UrlShortenerError::Cancelled)));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn test_gzip_export() {
+        use mockito::Matcher;
+
+        let mut server = mockito::Server::new_async().await;
+        let compressed = server
+            .mock("POST", "/export/ga/json")
+            .match_header("accept-encoding", Matcher::Regex("gzip".into()))
+            .with_header("content-encoding", "gzip")
+            .with_body(include_bytes!("fixtures/export.json.gz"))
+            .create_async()
+            .await;
+        let plain = server
+            .mock("POST", "/export/ga/json")
+            .match_header("accept-encoding", Matcher::Missing)
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 12, "total_unique_clicks": 7}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let export = client
+            .export(ExportRequest::new("ga", ExportFormat::JSON))
+            .await
+            .expect("Failed to export compressed stats");
+        let stats = export.as_stats().expect("Export wasn't decompressed");
+        assert_eq!(stats.total_clicks, 12);
+
+        let client = UrlShortenerClient::builder()
+            .base_url(server.url())
+            .decompression(false)
+            .build();
+        let uncompressed = client
+            .export(ExportRequest::new("ga", ExportFormat::JSON))
+            .await
+            .expect("Failed to export uncompressed stats");
+        assert_eq!(uncompressed.data(), export.data());
+
+        compressed.assert_async().await;
+        plain.assert_async().await;
+    }
+}
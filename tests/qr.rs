@@ -0,0 +1,75 @@
+#[cfg(feature = "qr")]
+#[cfg(test)]
+mod qr_tests {
+    use spoo_me::{
+        client::UrlShortenerClient,
+        errors::{UrlShortenerError, ValidationError},
+        requests::{QrFormat, QrOptions},
+    };
+
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    #[test]
+    fn test_qr_png() {
+        let client = UrlShortenerClient::new();
+        let qr = client
+            .qr("ga", &QrOptions::new())
+            .expect("Failed to render QR code");
+
+        assert_eq!(qr.format(), QrFormat::PNG);
+        assert!(qr.data().starts_with(PNG_SIGNATURE));
+    }
+
+    #[test]
+    fn test_qr_svg() {
+        let client = UrlShortenerClient::new();
+        let qr = client
+            .qr("ga", &QrOptions::new().format(QrFormat::SVG).size(128))
+            .expect("Failed to render QR code");
+
+        assert_eq!(qr.format(), QrFormat::SVG);
+        let svg = std::str::from_utf8(qr.data()).expect("SVG should be UTF-8");
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_qr_save_to_file() {
+        let client = UrlShortenerClient::new();
+        let qr = client
+            .qr("ga", &QrOptions::new())
+            .expect("Failed to render QR code");
+        let path = std::env::temp_dir().join("spoo_me_test_qr.png");
+
+        qr.save_to_file(&path).expect("Failed to save QR code");
+        let saved = std::fs::read(&path).expect("Failed to read QR code");
+        std::fs::remove_file(&path).ok();
+        assert_eq!(saved, qr.data());
+    }
+
+    #[test]
+    fn test_qr_image_error_source() {
+        use std::error::Error;
+
+        let image_err = image::ImageError::Limits(image::error::LimitError::from_kind(
+            image::error::LimitErrorKind::InsufficientMemory,
+        ));
+        let err = UrlShortenerError::from(image_err);
+
+        assert!(matches!(err, UrlShortenerError::QrImage(_)));
+        let source = err.source().expect("Expected a source error");
+        assert!(source.downcast_ref::<image::ImageError>().is_some());
+    }
+
+    #[test]
+    fn test_qr_invalid_short_code() {
+        let client = UrlShortenerClient::new();
+        let response = client.qr("bad code", &QrOptions::new());
+
+        assert!(matches!(
+            response,
+            Err(UrlShortenerError::Validation(
+                ValidationError::InvalidAliasFormat(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use spoo_me::retry::RetryPolicy;
+
+#[test]
+fn test_default_policy_never_retries() {
+    assert_eq!(RetryPolicy::default().max_retries, 0);
+    assert_eq!(RetryPolicy::default().max_transient_retries, 0);
+    assert_eq!(RetryPolicy::none(), RetryPolicy::default());
+    assert_eq!(RetryPolicy::new(3).max_retries, 3);
+}
+
+#[test]
+fn test_exponential_backoff() {
+    let policy = RetryPolicy {
+        max_retries: 5,
+        max_transient_retries: 0,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+        jitter: false,
+    };
+
+    assert_eq!(policy.delay_for(0), Duration::from_millis(100));
+    assert_eq!(policy.delay_for(1), Duration::from_millis(200));
+    assert_eq!(policy.delay_for(2), Duration::from_millis(400));
+    assert_eq!(policy.delay_for(3), Duration::from_millis(800));
+    assert_eq!(policy.delay_for(4), Duration::from_secs(1));
+    assert_eq!(policy.delay_for(u32::MAX), Duration::from_secs(1));
+}
+
+#[test]
+fn test_jitter_stays_in_bounds() {
+    let policy = RetryPolicy {
+        max_retries: 5,
+        max_transient_retries: 0,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+        jitter: true,
+    };
+
+    for _ in 0..100 {
+        let delay = policy.delay_for(2);
+        assert!(delay > Duration::from_millis(200) && delay <= Duration::from_millis(400));
+    }
+}
@@ -0,0 +1,283 @@
+use spoo_me::requests::{
+    EmojiRequest, EmojiResponse, ShortResult, ShortenOptions, ShortenRequest, ShortenResponse,
+};
+
+#[test]
+fn test_shorten_response_applied_options() {
+    let response: ShortenResponse = serde_json::from_str(
+        r#"{
+            "short_url": "https://spoo.me/ga",
+            "domain": "spoo.me",
+            "original_url": "https://google.com",
+            "password_protected": true,
+            "max_clicks": 100,
+            "block_bots": false
+        }"#,
+    )
+    .expect("Failed to deserialize shorten response");
+
+    assert_eq!(response.password_protected, Some(true));
+    assert_eq!(response.max_clicks, Some(100));
+    assert_eq!(response.block_bots, Some(false));
+}
+
+#[test]
+fn test_shorten_response_without_options() {
+    let response: ShortenResponse = serde_json::from_str(
+        r#"{"short_url": "https://spoo.me/ga", "domain": "spoo.me", "original_url": "https://google.com"}"#,
+    )
+    .expect("Failed to deserialize shorten response");
+
+    assert_eq!(response.short_url, "https://spoo.me/ga");
+    assert_eq!(response.password_protected, None);
+    assert_eq!(response.max_clicks, None);
+    assert_eq!(response.block_bots, None);
+}
+
+fn response_for(original_url: &str) -> ShortenResponse {
+    ShortenResponse {
+        short_url: "https://spoo.me/ga".to_string(),
+        domain: "spoo.me".to_string(),
+        original_url: original_url.to_string(),
+        password_protected: None,
+        max_clicks: None,
+        block_bots: None,
+    }
+}
+
+#[test]
+fn test_matches_input_equivalent_urls() {
+    let response = response_for("https://example.com/path/");
+    assert!(response.matches_input("https://example.com/path/"));
+    assert!(response.matches_input("https://example.com/path"));
+    assert!(response.matches_input("HTTPS://Example.COM:443/path"));
+
+    let response = response_for("https://example.com/");
+    assert!(response.matches_input("https://example.com"));
+}
+
+#[test]
+fn test_matches_input_different_urls() {
+    let response = response_for("https://example.com/path");
+    assert!(!response.matches_input("http://example.com/path"));
+    assert!(!response.matches_input("https://example.com:8443/path"));
+    assert!(!response.matches_input("https://example.com/other"));
+    assert!(!response.matches_input("https://example.com/path?q=1"));
+    assert!(!response.matches_input("not a url"));
+}
+
+#[test]
+fn test_matches_input_unparseable_original() {
+    let response = response_for("not a url");
+    assert!(response.matches_input("not a url"));
+    assert!(!response.matches_input("https://example.com"));
+}
+
+#[test]
+fn test_short_code() {
+    let mut response = response_for("https://example.com");
+    assert_eq!(response.short_code(), Some("ga".to_string()));
+
+    response.short_url = "https://self.hosted/spoo/abc_123/".to_string();
+    assert_eq!(response.short_code(), Some("abc_123".to_string()));
+
+    response.short_url = "https://spoo.me/".to_string();
+    assert_eq!(response.short_code(), None);
+
+    response.short_url = "not a url".to_string();
+    assert_eq!(response.short_code(), None);
+}
+
+#[test]
+fn test_emoji_short_code() {
+    let mut response = EmojiResponse {
+        short_url: "https://spoo.me/🍕🔥".to_string(),
+        domain: "spoo.me".to_string(),
+        original_url: "https://example.com".to_string(),
+    };
+    assert_eq!(response.short_code(), Some("🍕🔥".to_string()));
+
+    response.short_url = "https://spoo.me/%F0%9F%87%AB%F0%9F%87%B7".to_string();
+    assert_eq!(response.short_code(), Some("🇫🇷".to_string()));
+}
+
+#[test]
+fn test_is_on() {
+    let mut response = response_for("https://example.com");
+    assert!(response.is_on("https://spoo.me"));
+    assert!(response.is_on("https://SPOO.me/"));
+    assert!(response.is_on("spoo.me"));
+    assert!(!response.is_on("https://self.hosted"));
+    assert!(!response.is_on("https://spoo.me:8443"));
+
+    response.short_url = "http://self.hosted:8000/abc".to_string();
+    assert!(response.is_on("http://self.hosted:8000/spoo"));
+    assert!(!response.is_on("http://self.hosted"));
+    assert!(!response.is_on("https://spoo.me"));
+
+    response.short_url = "not a url".to_string();
+    assert!(!response.is_on("https://spoo.me"));
+}
+
+#[test]
+fn test_emoji_is_on() {
+    let response = EmojiResponse {
+        short_url: "https://self.hosted/🍕🔥".to_string(),
+        domain: "self.hosted".to_string(),
+        original_url: "https://example.com".to_string(),
+    };
+    assert!(response.is_on("https://self.hosted"));
+    assert!(!response.is_on("https://spoo.me"));
+}
+
+#[test]
+fn test_short_result() {
+    fn summary(result: &dyn ShortResult) -> (String, String, String, Option<String>) {
+        (
+            result.short_url().to_string(),
+            result.domain().to_string(),
+            result.original_url().to_string(),
+            result.short_code(),
+        )
+    }
+
+    let shortened = response_for("https://example.com");
+    let emoji = EmojiResponse {
+        short_url: "https://spoo.me/🍕🔥".to_string(),
+        domain: "spoo.me".to_string(),
+        original_url: "https://example.com".to_string(),
+    };
+
+    assert_eq!(
+        summary(&shortened),
+        (
+            "https://spoo.me/ga".to_string(),
+            "spoo.me".to_string(),
+            "https://example.com".to_string(),
+            Some("ga".to_string()),
+        )
+    );
+    assert_eq!(
+        summary(&emoji),
+        (
+            "https://spoo.me/🍕🔥".to_string(),
+            "spoo.me".to_string(),
+            "https://example.com".to_string(),
+            Some("🍕🔥".to_string()),
+        )
+    );
+    assert!(ShortResult::is_on(&emoji, "spoo.me"));
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(
+        response_for("https://example.com").to_string(),
+        "https://spoo.me/ga"
+    );
+
+    let response = EmojiResponse {
+        short_url: "https://spoo.me/🍕🔥".to_string(),
+        domain: "spoo.me".to_string(),
+        original_url: "https://example.com".to_string(),
+    };
+    assert_eq!(response.to_string(), "https://spoo.me/🍕🔥");
+}
+
+#[test]
+fn test_equality() {
+    let request = ShortenRequest::new("https://example.com").alias("ga");
+    assert_eq!(request, request.clone());
+    assert_ne!(request, request.clone().max_clicks(5));
+    assert_ne!(
+        EmojiRequest::new("https://example.com"),
+        EmojiRequest::new("https://example.com").emojies("🍕")
+    );
+
+    let response = response_for("https://example.com");
+    assert_eq!(response, response_for("https://example.com"));
+    assert_ne!(response, response_for("https://example.org"));
+}
+
+#[test]
+fn test_request_getters() {
+    let request = ShortenRequest::new("https://example.com");
+    assert_eq!(request.url(), "https://example.com");
+    assert_eq!(request.get_alias(), None);
+    assert!(!request.has_password());
+    assert_eq!(request.get_max_clicks(), None);
+    assert_eq!(request.get_block_bots(), None);
+
+    let request = request
+        .alias("ga")
+        .password("Valid@123")
+        .max_clicks(5)
+        .block_bots(true);
+    assert_eq!(request.get_alias(), Some("ga"));
+    assert!(request.has_password());
+    assert_eq!(request.get_max_clicks(), Some(5));
+    assert_eq!(request.get_block_bots(), Some(true));
+
+    let request = EmojiRequest::new("https://example.com")
+        .emojies("🍕")
+        .password("Valid@123")
+        .max_clicks(1)
+        .block_bots(false);
+    assert_eq!(request.url(), "https://example.com");
+    assert_eq!(request.get_emojies(), Some("🍕"));
+    assert!(request.has_password());
+    assert_eq!(request.get_max_clicks(), Some(1));
+    assert_eq!(request.get_block_bots(), Some(false));
+}
+
+#[test]
+fn test_new_blocking_bots() {
+    let request = ShortenRequest::new_blocking_bots("https://example.com");
+    assert_eq!(request.url(), "https://example.com");
+    assert_eq!(request.get_block_bots(), Some(true));
+    assert_eq!(
+        request,
+        ShortenRequest::new("https://example.com").block_bots(true)
+    );
+    assert_eq!(
+        ShortenRequest::new("https://example.com").get_block_bots(),
+        None
+    );
+}
+
+#[test]
+fn test_idempotency_key() {
+    let request = ShortenRequest::new("https://example.com");
+    assert_eq!(request.get_idempotency_key(), None);
+
+    let request = request.idempotency_key("key-123");
+    assert_eq!(request.get_idempotency_key(), Some("key-123"));
+
+    let first = ShortenRequest::new("https://example.com").generate_idempotency_key();
+    let second = ShortenRequest::new("https://example.com").generate_idempotency_key();
+    assert_eq!(first.get_idempotency_key().unwrap().len(), 36);
+    assert_ne!(first.get_idempotency_key(), second.get_idempotency_key());
+}
+
+#[test]
+fn test_with_options() {
+    let options: ShortenOptions = serde_json::from_str(
+        r#"{"alias": "docs-link", "password": "Secret@123", "max-clicks": 10, "block_bots": true}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        ShortenRequest::with_options("https://example.com", options),
+        ShortenRequest::new("https://example.com")
+            .alias("docs-link")
+            .password("Secret@123")
+            .max_clicks(10)
+            .block_bots(true)
+    );
+
+    let options: ShortenOptions = serde_json::from_str("{}").unwrap();
+    assert_eq!(options, ShortenOptions::default());
+    assert_eq!(
+        ShortenRequest::with_options("https://example.com", options),
+        ShortenRequest::new("https://example.com")
+    );
+}
@@ -0,0 +1,136 @@
+use spoo_me::{
+    errors::UrlShortenerError,
+    requests::{
+        EmojiRequest, EmojiResponse, ExportRequest, ExportResponse, ShortenRequest,
+        ShortenResponse, StatsRequest, StatsResponse,
+    },
+};
+
+/// A fake shortener that hands out sequential short codes without touching the network.
+#[derive(Default)]
+struct MockShortener {
+    calls: std::sync::atomic::AtomicUsize,
+}
+
+impl MockShortener {
+    fn next_response(&self, url: &str) -> ShortenResponse {
+        let n = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        ShortenResponse {
+            short_url: format!("https://spoo.me/mock{}", n),
+            domain: "spoo.me".to_string(),
+            original_url: url.to_string(),
+            password_protected: None,
+            max_clicks: None,
+            block_bots: None,
+        }
+    }
+}
+
+fn not_mocked<T>() -> Result<T, UrlShortenerError> {
+    Err(UrlShortenerError::Other("not mocked".to_string()))
+}
+
+#[cfg(not(feature = "blocking"))]
+mod async_shortener_tests {
+    use super::*;
+    use spoo_me::{client::UrlShortenerClient, shortener::UrlShortener};
+
+    #[async_trait::async_trait]
+    impl UrlShortener for MockShortener {
+        async fn shorten(
+            &self,
+            _req: ShortenRequest,
+        ) -> Result<ShortenResponse, UrlShortenerError> {
+            Ok(self.next_response("https://example.com"))
+        }
+
+        async fn emoji(&self, _req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
+            not_mocked()
+        }
+
+        async fn stats(&self, _req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
+            not_mocked()
+        }
+
+        async fn export(&self, _req: ExportRequest) -> Result<ExportResponse, UrlShortenerError> {
+            not_mocked()
+        }
+    }
+
+    /// Application code that only depends on the trait.
+    async fn share_links(shortener: &dyn UrlShortener, urls: &[&str]) -> Vec<String> {
+        let mut links = Vec::new();
+        for url in urls {
+            if let Ok(response) = shortener.shorten(ShortenRequest::new(*url)).await {
+                links.push(response.short_url);
+            }
+        }
+        links
+    }
+
+    #[tokio::test]
+    async fn test_mock_shortener() {
+        let mock = MockShortener::default();
+        let links = share_links(&mock, &["https://example.com/a", "https://example.com/b"]).await;
+
+        assert_eq!(
+            links,
+            vec!["https://spoo.me/mock0", "https://spoo.me/mock1"]
+        );
+        assert!(mock.stats(StatsRequest::new("ga")).await.is_err());
+    }
+
+    #[test]
+    fn test_client_implements_trait() {
+        fn assert_shortener<T: UrlShortener>(_: &T) {}
+        assert_shortener(&UrlShortenerClient::new());
+    }
+}
+
+#[cfg(feature = "blocking")]
+mod blocking_shortener_tests {
+    use super::*;
+    use spoo_me::{client::UrlShortenerClient, shortener::BlockingUrlShortener};
+
+    impl BlockingUrlShortener for MockShortener {
+        fn shorten_blocking(
+            &self,
+            _req: ShortenRequest,
+        ) -> Result<ShortenResponse, UrlShortenerError> {
+            Ok(self.next_response("https://example.com"))
+        }
+
+        fn emoji_blocking(&self, _req: EmojiRequest) -> Result<EmojiResponse, UrlShortenerError> {
+            not_mocked()
+        }
+
+        fn stats_blocking(&self, _req: StatsRequest) -> Result<StatsResponse, UrlShortenerError> {
+            not_mocked()
+        }
+
+        fn export_blocking(
+            &self,
+            _req: ExportRequest,
+        ) -> Result<ExportResponse, UrlShortenerError> {
+            not_mocked()
+        }
+    }
+
+    #[test]
+    fn test_mock_shortener_blocking() {
+        let mock = MockShortener::default();
+        let shortener: &dyn BlockingUrlShortener = &mock;
+
+        let response = shortener
+            .shorten_blocking(ShortenRequest::new("https://example.com"))
+            .unwrap();
+        assert_eq!(response.short_url, "https://spoo.me/mock0");
+        assert!(shortener.stats_blocking(StatsRequest::new("ga")).is_err());
+    }
+
+    #[test]
+    fn test_client_implements_trait() {
+        fn assert_shortener<T: BlockingUrlShortener>(_: &T) {}
+        assert_shortener(&UrlShortenerClient::new());
+    }
+}
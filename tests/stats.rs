@@ -0,0 +1,446 @@
+use spoo_me::requests::{BotType, StatsRequest, StatsResponse};
+
+fn stats_with_counter(counter: &str) -> StatsResponse {
+    serde_json::from_str(&format!(
+        r#"{{
+            "short_code": "ga",
+            "url": "https://google.com",
+            "total-clicks": 10,
+            "total_unique_clicks": 5,
+            "counter": {}
+        }}"#,
+        counter
+    ))
+    .expect("Failed to deserialize stats fixture")
+}
+
+#[test]
+fn test_top_entries() {
+    let mut stats = stats_with_counter(r#"{"2024-02-27": 3, "2024-02-28": 6}"#);
+    stats.country = Some(
+        [("US", 5), ("DE", 2), ("IN", 5), ("FR", 1)]
+            .into_iter()
+            .map(|(name, clicks)| (name.to_string(), clicks))
+            .collect(),
+    );
+
+    assert_eq!(
+        stats.top_countries(3),
+        vec![
+            ("IN".to_string(), 5),
+            ("US".to_string(), 5),
+            ("DE".to_string(), 2)
+        ]
+    );
+    assert_eq!(stats.top_countries(10).len(), 4);
+    assert!(stats.top_browsers(3).is_empty());
+}
+
+#[test]
+fn test_clicks_on_and_total_from_counter() {
+    let stats = stats_with_counter(r#"{"2024-02-27": 3, "2024-02-28": 6}"#);
+    assert_eq!(stats.clicks_on("2024-02-28"), 6);
+    assert_eq!(stats.clicks_on("2024-03-01"), 0);
+    assert_eq!(stats.total_from_counter(), 9);
+
+    let stats = stats_with_counter("null");
+    assert_eq!(stats.clicks_on("2024-02-28"), 0);
+    assert_eq!(stats.total_from_counter(), 0);
+}
+
+#[test]
+fn test_counter_between() {
+    let mut stats = stats_with_counter(
+        r#"{"2023-12-31": 1, "2024-01-01": 2, "2024-01-15": 3, "2024-01-31": 4, "2024-02-01": 5, "total": 15}"#,
+    );
+    stats.unique_counter = Some(
+        [("2024-01-01", 1), ("2024-02-01", 2)]
+            .into_iter()
+            .map(|(day, clicks)| (day.to_string(), clicks))
+            .collect(),
+    );
+
+    let january = stats.counter_between("2024-01-01", "2024-01-31");
+    assert_eq!(january.len(), 3);
+    assert_eq!(january["2024-01-01"], 2);
+    assert_eq!(january["2024-01-31"], 4);
+    assert!(!january.contains_key("2023-12-31"));
+    assert!(!january.contains_key("2024-02-01"));
+
+    assert_eq!(
+        stats.counter_between("2024-01-15", "2024-01-15"),
+        [("2024-01-15".to_string(), 3)].into_iter().collect()
+    );
+    assert!(stats.counter_between("2024-01-31", "2024-01-01").is_empty());
+    assert_eq!(stats.counter_between("0000-01-01", "9999-12-31").len(), 5);
+
+    let unique = stats.unique_counter_between("2024-01-01", "2024-01-31");
+    assert_eq!(
+        unique,
+        [("2024-01-01".to_string(), 1)].into_iter().collect()
+    );
+
+    stats.counter = None;
+    assert!(stats.counter_between("2024-01-01", "2024-01-31").is_empty());
+}
+
+#[test]
+fn test_counter_sorted() {
+    let stats = stats_with_counter(
+        r#"{"2024-03-01": 2, "2024-02-27": 3, "2024-12-31": 5, "2024-02-28": 1}"#,
+    );
+    let days: Vec<_> = stats.counter_sorted().into_iter().collect();
+    assert_eq!(
+        days,
+        vec![
+            ("2024-02-27".to_string(), 3),
+            ("2024-02-28".to_string(), 1),
+            ("2024-03-01".to_string(), 2),
+            ("2024-12-31".to_string(), 5),
+        ]
+    );
+    assert!(stats_with_counter("null").counter_sorted().is_empty());
+}
+
+#[test]
+fn test_equality() {
+    let stats = stats_with_counter(r#"{"2024-02-27": 3, "2024-02-28": 6}"#);
+    assert_eq!(
+        stats,
+        stats_with_counter(r#"{"2024-02-28": 6, "2024-02-27": 3}"#)
+    );
+    assert_ne!(stats, stats_with_counter(r#"{"2024-02-27": 3}"#));
+}
+
+#[test]
+fn test_display() {
+    let stats = stats_with_counter("null");
+    assert_eq!(stats.to_string(), "ga: 10 clicks (5 unique)");
+}
+
+#[test]
+fn test_clicks_remaining() {
+    let mut stats = stats_with_counter("null");
+    assert_eq!(stats.clicks_remaining(), None);
+    assert!(!stats.is_exhausted());
+
+    stats.max_clicks = Some(25);
+    assert_eq!(stats.clicks_remaining(), Some(15));
+    assert!(!stats.is_exhausted());
+
+    stats.max_clicks = Some(10);
+    assert_eq!(stats.clicks_remaining(), Some(0));
+    assert!(stats.is_exhausted());
+
+    stats.max_clicks = Some(4);
+    assert_eq!(stats.clicks_remaining(), Some(0));
+    assert!(stats.is_exhausted());
+}
+
+#[test]
+fn test_bot_type_parse() {
+    assert_eq!(BotType::parse("Googlebot"), BotType::Search);
+    assert_eq!(BotType::parse("bingbot"), BotType::Search);
+    assert_eq!(BotType::parse("YandexBot"), BotType::Search);
+    assert_eq!(BotType::parse("facebookexternalhit"), BotType::Social);
+    assert_eq!(BotType::parse("Twitterbot"), BotType::Social);
+    assert_eq!(BotType::parse("Slackbot-LinkExpanding"), BotType::Social);
+    assert_eq!(BotType::parse("UptimeRobot"), BotType::Monitoring);
+    assert_eq!(BotType::parse("Pingdom.com_bot"), BotType::Monitoring);
+    assert_eq!(
+        BotType::parse(" SomeCrawler "),
+        BotType::Unknown("SomeCrawler".to_string())
+    );
+    assert_eq!("Discordbot".parse(), Ok(BotType::Social));
+}
+
+#[test]
+fn test_bots_typed() {
+    let mut stats = stats_with_counter("null");
+    assert!(stats.bots_typed().is_empty());
+
+    stats.bots = Some(
+        [
+            ("Googlebot", 4),
+            ("Bingbot", 1),
+            ("Twitterbot", 2),
+            ("UptimeRobot", 7),
+            ("SomeCrawler", 3),
+        ]
+        .into_iter()
+        .map(|(name, clicks)| (name.to_string(), clicks))
+        .collect(),
+    );
+    let bots = stats.bots_typed();
+    assert_eq!(bots.len(), 4);
+    assert_eq!(bots[&BotType::Search], 5);
+    assert_eq!(bots[&BotType::Social], 2);
+    assert_eq!(bots[&BotType::Monitoring], 7);
+    assert_eq!(bots[&BotType::Unknown("SomeCrawler".to_string())], 3);
+    assert_eq!(stats.bots.as_ref().map(|bots| bots.len()), Some(5));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_daily_series_filled() {
+    use chrono::NaiveDate;
+
+    let day = |d: u32| NaiveDate::from_ymd_opt(2024, 2, d).unwrap();
+    let stats = stats_with_counter(r#"{"2024-02-27": 3, "2024-03-01": 2, "2024-02-28": 1}"#);
+
+    assert_eq!(
+        stats.daily_series_filled(),
+        vec![
+            (day(27), 3),
+            (day(28), 1),
+            (day(29), 0),
+            (NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), 2),
+        ]
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_daily_series_filled_empty() {
+    assert!(stats_with_counter("{}").daily_series_filled().is_empty());
+    assert!(stats_with_counter("null").daily_series_filled().is_empty());
+    assert!(stats_with_counter(r#"{"not-a-date": 4}"#)
+        .daily_series_filled()
+        .is_empty());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_counter_by_date() {
+    use chrono::NaiveDate;
+
+    let day = |m: u32, d: u32| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+    let stats = stats_with_counter(
+        r#"{"2024-03-01": 2, "2024-02-27": 3, "not-a-date": 4, "2024-02-28": 1}"#,
+    );
+
+    let days: Vec<_> = stats.counter_by_date().into_iter().collect();
+    assert_eq!(days, vec![(day(2, 27), 3), (day(2, 28), 1), (day(3, 1), 2)]);
+    assert!(stats_with_counter("null").counter_by_date().is_empty());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_counter_chunks() {
+    use chrono::NaiveDate;
+
+    let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+    let stats = stats_with_counter(
+        r#"{"2024-03-04": 4, "2024-03-01": 1, "2024-03-03": 3, "2024-03-02": 2}"#,
+    );
+
+    let chunks: Vec<_> = stats.counter_chunks(2).collect();
+    assert_eq!(
+        chunks,
+        vec![
+            vec![(day(1), 1), (day(2), 2)],
+            vec![(day(3), 3), (day(4), 4)]
+        ]
+    );
+
+    let chunks: Vec<_> = stats.counter_chunks(3).collect();
+    assert_eq!(
+        chunks,
+        vec![
+            vec![(day(1), 1), (day(2), 2), (day(3), 3)],
+            vec![(day(4), 4)]
+        ]
+    );
+
+    assert_eq!(stats.counter_chunks(10).count(), 1);
+    assert_eq!(stats_with_counter("null").counter_chunks(7).count(), 0);
+    assert_eq!(stats.counter_chunks(0).count(), 0);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_clicks_last_days() {
+    let stats = stats_with_counter(
+        r#"{"2024-03-01": 1, "2024-03-05": 2, "2024-03-06": 4, "2024-03-07": 8}"#,
+    );
+
+    assert_eq!(stats.clicks_last_days(0), 0);
+    assert_eq!(stats.clicks_last_days(1), 8);
+    assert_eq!(stats.clicks_last_days(3), 14);
+    assert_eq!(stats.clicks_last_days(7), 15);
+    assert_eq!(stats.clicks_last_days(365), 15);
+    assert_eq!(stats_with_counter("null").clicks_last_days(7), 0);
+}
+
+#[cfg(feature = "chrono")]
+fn stats_with_dates(creation_date: &str, last_click: &str) -> StatsResponse {
+    serde_json::from_value(serde_json::json!({
+        "short_code": "ga",
+        "url": "https://google.com",
+        "total-clicks": 10,
+        "total_unique_clicks": 5,
+        "creation-date": creation_date,
+        "last-click": last_click,
+    }))
+    .expect("Failed to deserialize stats fixture")
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_datetimes() {
+    use chrono::{TimeZone, Utc};
+
+    let stats = stats_with_dates("2024-02-27", "2024-03-01 13:45:10");
+    assert_eq!(
+        stats.creation_datetime(),
+        Some(Utc.with_ymd_and_hms(2024, 2, 27, 0, 0, 0).unwrap())
+    );
+    assert_eq!(
+        stats.last_click_datetime(),
+        Some(Utc.with_ymd_and_hms(2024, 3, 1, 13, 45, 10).unwrap())
+    );
+
+    let stats = stats_with_dates("2024-02-27T10:00:00+02:00", "");
+    assert_eq!(
+        stats.creation_datetime(),
+        Some(Utc.with_ymd_and_hms(2024, 2, 27, 8, 0, 0).unwrap())
+    );
+    assert_eq!(stats.last_click_datetime(), None);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_datetimes_unrecognized() {
+    let stats = stats_with_dates("27/02/2024", "yesterday");
+    assert_eq!(stats.creation_datetime(), None);
+    assert_eq!(stats.last_click_datetime(), None);
+
+    let stats = stats_with_counter("{}");
+    assert_eq!(stats.creation_datetime(), None);
+    assert_eq!(stats.last_click_datetime(), None);
+}
+
+#[cfg(feature = "isocountry")]
+#[test]
+fn test_countries_iso() {
+    use spoo_me::requests::CountryCode;
+
+    let mut stats = stats_with_counter("null");
+    stats.country = Some(
+        [("US", 2), ("USA", 3), ("germany", 1), ("Atlantis", 4)]
+            .into_iter()
+            .map(|(name, clicks)| (name.to_string(), clicks))
+            .collect(),
+    );
+
+    let countries = stats.countries_iso();
+    assert_eq!(countries.len(), 3);
+    assert_eq!(
+        countries[&CountryCode::Iso(isocountry::CountryCode::USA)],
+        5
+    );
+    assert_eq!(
+        countries[&CountryCode::Iso(isocountry::CountryCode::DEU)],
+        1
+    );
+    assert_eq!(countries[&CountryCode::Unknown("Atlantis".to_string())], 4);
+    assert_eq!(stats.country.unwrap().len(), 4);
+}
+
+#[test]
+fn test_stats_request_with_password() {
+    assert_eq!(
+        StatsRequest::with_password("ga", "Valid@123"),
+        StatsRequest::new("ga").password("Valid@123")
+    );
+}
+
+#[test]
+fn test_extra_fields() {
+    let stats: StatsResponse = serde_json::from_str(
+        r#"{
+            "short_code": "ga",
+            "url": "https://google.com",
+            "total-clicks": 10,
+            "total_unique_clicks": 5,
+            "unique_device": {"mobile": 3, "desktop": 2}
+        }"#,
+    )
+    .expect("Failed to deserialize stats fixture");
+
+    assert_eq!(stats.extra.len(), 1);
+    assert_eq!(
+        stats.extra["unique_device"],
+        serde_json::json!({"mobile": 3, "desktop": 2})
+    );
+    assert!(stats_with_counter("null").extra.is_empty());
+
+    let round_trip: StatsResponse =
+        serde_json::from_value(serde_json::to_value(&stats).unwrap()).unwrap();
+    assert_eq!(round_trip, stats);
+}
+
+#[test]
+fn test_struct_literal() {
+    let stats = StatsResponse {
+        short_code: "ga".to_string(),
+        url: "https://google.com".to_string(),
+        total_clicks: 10,
+        total_unique_clicks: 5,
+        creation_date: None,
+        expired: None,
+        last_click: None,
+        last_click_browser: None,
+        last_click_os: None,
+        max_clicks: None,
+        password: None,
+        block_bots: None,
+        bots: None,
+        browser: None,
+        country: None,
+        counter: None,
+        unique_browser: None,
+        unique_country: None,
+        unique_counter: None,
+        unique_os_name: None,
+        unique_referrer: None,
+        extra: Default::default(),
+    };
+
+    assert_eq!(stats, stats_with_counter("null"));
+}
+
+#[test]
+fn test_numbers_as_strings() {
+    let numbers: StatsResponse = serde_json::from_str(
+        r#"{
+            "short_code": "ga",
+            "url": "https://google.com",
+            "total-clicks": 42,
+            "total_unique_clicks": 7,
+            "max-clicks": 100
+        }"#,
+    )
+    .expect("Failed to deserialize numeric stats");
+    let strings: StatsResponse = serde_json::from_str(
+        r#"{
+            "short_code": "ga",
+            "url": "https://google.com",
+            "total-clicks": "42",
+            "total_unique_clicks": "7",
+            "max-clicks": "100"
+        }"#,
+    )
+    .expect("Failed to deserialize stats sent as strings");
+
+    assert_eq!(strings, numbers);
+    assert_eq!(strings.total_clicks, 42);
+    assert_eq!(strings.total_unique_clicks, 7);
+    assert_eq!(strings.max_clicks, Some(100));
+    assert_eq!(stats_with_counter("null").max_clicks, None);
+
+    let invalid = serde_json::from_str::<StatsResponse>(
+        r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": "many", "total_unique_clicks": 7}"#,
+    );
+    assert!(invalid.is_err());
+}
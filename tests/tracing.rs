@@ -0,0 +1,92 @@
+#[cfg(all(feature = "tracing", feature = "custom_url", not(feature = "blocking")))]
+#[cfg(test)]
+mod tracing_tests {
+    use std::{
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    use spoo_me::{client::UrlShortenerClient, requests::StatsRequest};
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+    /// Collects the names and fields of all spans and events as `name=value` strings.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    struct Fields<'a>(&'a mut Vec<String>);
+
+    impl Visit for Fields<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={}", field.name(), value));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            let mut lines = self.0.lock().unwrap();
+            lines.push(format!("span={}", attrs.metadata().name()));
+            attrs.record(&mut Fields(&mut lines));
+        }
+
+        fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            values.record(&mut Fields(&mut self.0.lock().unwrap()));
+        }
+
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            event.record(&mut Fields(&mut self.0.lock().unwrap()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_span() {
+        let capture = Capture::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/stats/ga")
+            .with_body(r#"{"short_code": "ga", "url": "https://google.com", "total-clicks": 1, "total_unique_clicks": 1}"#)
+            .create_async()
+            .await;
+
+        let client = UrlShortenerClient::new_with_base_url(server.url());
+        let response = client
+            .stats(StatsRequest::new("ga").password("Secret@123"))
+            .await;
+
+        mock.assert_async().await;
+        assert!(
+            response.is_ok(),
+            "Failed to get stats: {:?}",
+            response.err()
+        );
+
+        let lines = capture.0.lock().unwrap();
+        for expected in [
+            "span=spoo_me.request",
+            "endpoint=stats",
+            "short_code=ga",
+            "status=200",
+            "message=request succeeded",
+        ] {
+            assert!(
+                lines.iter().any(|line| line == expected),
+                "Missing {:?} in {:?}",
+                expected,
+                lines
+            );
+        }
+        assert!(lines.iter().any(|line| line.starts_with("elapsed_ms=")));
+        assert!(!lines.iter().any(|line| line.contains("Secret@123")));
+    }
+}
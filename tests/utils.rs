@@ -1,4 +1,8 @@
-use spoo_me::utils::*;
+use spoo_me::{
+    errors::{PasswordViolation, ValidationError},
+    requests::ExportFormat,
+    utils::*,
+};
 
 #[test]
 fn test_valid_password() {
@@ -9,25 +13,104 @@ fn test_valid_password() {
     assert!(!is_valid_password("Invalid@@@"));
 }
 
+#[test]
+fn test_validate_password() {
+    assert_eq!(validate_password("Valid@123"), Ok(()));
+    assert_eq!(validate_password("V@1"), Err(PasswordViolation::TooShort));
+    assert_eq!(
+        validate_password("12345@678"),
+        Err(PasswordViolation::MissingLetter)
+    );
+    assert_eq!(
+        validate_password("NoDigit@"),
+        Err(PasswordViolation::MissingDigit)
+    );
+    assert_eq!(
+        validate_password("NoSpecial123"),
+        Err(PasswordViolation::MissingSpecial)
+    );
+    assert_eq!(
+        validate_password("Invalid@@1"),
+        Err(PasswordViolation::ConsecutiveSpecial)
+    );
+    assert_eq!(
+        ValidationError::InvalidPasswordFormat(PasswordViolation::MissingDigit).to_string(),
+        "Invalid password format: must contain a digit"
+    );
+}
+
+#[test]
+fn test_validate_passwords() {
+    let results = validate_passwords(&["Valid@123", "short", "Other.456"]);
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        results[1],
+        Err(ValidationError::InvalidPasswordFormat(
+            PasswordViolation::TooShort
+        ))
+    ));
+    assert!(results[2].is_ok());
+    assert!(validate_passwords(&[]).is_empty());
+}
+
 #[cfg(feature = "custom_url")]
 #[test]
 fn test_valid_url() {
     assert!(is_valid_url("https://example.com", "spoo.me"));
-    assert!(is_valid_url("ftp://example.com", "spoo.me"));
+    assert!(!is_valid_url("ftp://example.com", "spoo.me"));
     assert!(!is_valid_url("https://spoo.me/test", "spoo.me"));
     assert!(!is_valid_url("https://example.com/..", "spoo.me"));
 }
 
+#[cfg(feature = "custom_url")]
+#[test]
+fn test_url_schemes() {
+    for (url, valid, valid_with_ftp) in [
+        ("http://example.com", true, true),
+        ("https://example.com", true, true),
+        ("ftp://example.com", false, true),
+        ("file:///etc/passwd", false, false),
+        ("ws://example.com", false, false),
+        ("mailto:someone@example.com", false, false),
+    ] {
+        assert_eq!(is_valid_url(url, "spoo.me"), valid, "{url}");
+        assert_eq!(
+            is_valid_url_allowing_ftp(url, "spoo.me"),
+            valid_with_ftp,
+            "{url}"
+        );
+    }
+    assert!(!is_valid_url_allowing_ftp("ftp://spoo.me/test", "spoo.me"));
+}
+
 #[cfg(not(feature = "custom_url"))]
 #[test]
 fn test_valid_url() {
     assert!(is_valid_url("https://example.com"));
-    assert!(is_valid_url("ftp://example.com"));
+    assert!(!is_valid_url("ftp://example.com"));
     assert!(is_valid_url("https://example.com/long/url"));
     assert!(!is_valid_url("https://spoo.me/test"));
     assert!(!is_valid_url("https://example.com/.."));
 }
 
+#[cfg(not(feature = "custom_url"))]
+#[test]
+fn test_url_schemes() {
+    for (url, valid, valid_with_ftp) in [
+        ("http://example.com", true, true),
+        ("https://example.com", true, true),
+        ("ftp://example.com", false, true),
+        ("file:///etc/passwd", false, false),
+        ("ws://example.com", false, false),
+        ("mailto:someone@example.com", false, false),
+    ] {
+        assert_eq!(is_valid_url(url), valid, "{url}");
+        assert_eq!(is_valid_url_allowing_ftp(url), valid_with_ftp, "{url}");
+    }
+    assert!(!is_valid_url_allowing_ftp("ftp://spoo.me/test"));
+}
+
 #[test]
 fn test_valid_alias() {
     assert!(is_valid_alias("valid_alias"));
@@ -36,3 +119,151 @@ fn test_valid_alias() {
     assert!(!is_valid_alias("too_long_alias_12345"));
     assert!(!is_valid_alias(""));
 }
+
+#[test]
+fn test_reserved_alias() {
+    for alias in RESERVED_ALIASES {
+        assert!(is_reserved_alias(alias), "{} should be reserved", alias);
+    }
+    assert!(!is_reserved_alias("my_alias"));
+    assert!(!is_reserved_alias("statistics"));
+}
+
+#[test]
+fn test_valid_alias_repeated() {
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for i in 0..1000 {
+                    assert!(is_valid_alias(&format!("alias_{i}")));
+                    assert!(!is_valid_alias(&format!("alias {i}")));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_safe_filename() {
+    assert_eq!(safe_filename("ga", &ExportFormat::JSON), "ga.json");
+    assert_eq!(safe_filename("ga", &ExportFormat::CSV), "ga.zip");
+    assert_eq!(
+        safe_filename("my-code_1", &ExportFormat::XLSX),
+        "my-code_5f_1.xlsx"
+    );
+    assert_eq!(
+        safe_filename("🔥🚀", &ExportFormat::XML),
+        "_1f525__1f680_.xml"
+    );
+    assert_eq!(
+        safe_filename("../x", &ExportFormat::JSON),
+        "_2e__2e__2f_x.json"
+    );
+    assert_ne!(
+        safe_filename("_1f525", &ExportFormat::JSON),
+        safe_filename("🔥", &ExportFormat::JSON)
+    );
+    assert_ne!(
+        safe_filename("é1", &ExportFormat::JSON),
+        safe_filename("\u{e91}", &ExportFormat::JSON)
+    );
+    assert_eq!(safe_filename("CON", &ExportFormat::JSON), "_CON.json");
+    assert_eq!(safe_filename("", &ExportFormat::JSON), "export.json");
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn test_normalize_url() {
+    let defaults = NormalizeOptions::default();
+    assert_eq!(
+        normalize_url("https://EXAMPLE.com//a//b?utm_source=x", &defaults).as_deref(),
+        Some("https://example.com/a/b?utm_source=x")
+    );
+
+    let strip = NormalizeOptions {
+        strip_utm_params: true,
+        remove_duplicate_slashes: false,
+    };
+    assert_eq!(
+        normalize_url(
+            "https://example.com//a?utm_source=x&id=1&utm_medium=y",
+            &strip
+        )
+        .as_deref(),
+        Some("https://example.com//a?id=1")
+    );
+    assert_eq!(
+        normalize_url("https://example.com/a?utm_source=x", &strip).as_deref(),
+        Some("https://example.com/a")
+    );
+    assert_eq!(normalize_url("not a url", &defaults), None);
+}
+
+#[test]
+fn test_valid_emoji() {
+    assert!(is_valid_emoji("🔥"));
+    assert!(is_valid_emoji("😀🎉🚀"));
+    assert!(is_valid_emoji("👍🏽"));
+    assert!(is_valid_emoji("🇫🇷"));
+    assert!(is_valid_emoji("👨‍👩‍👧"));
+    assert!(is_valid_emoji("❤️1️⃣"));
+    assert!(!is_valid_emoji("abc"));
+    assert!(!is_valid_emoji("fire🔥"));
+    assert!(!is_valid_emoji("🔥 🔥"));
+    assert!(!is_valid_emoji("1"));
+    assert!(!is_valid_emoji(""));
+}
+
+#[test]
+fn test_emoji_count() {
+    assert_eq!(emoji_count("🔥"), 1);
+    assert_eq!(emoji_count("🇫🇷"), 1);
+    assert_eq!(emoji_count("👨‍👩‍👧"), 1);
+    assert_eq!(emoji_count("🍕🔥👍🏽"), 3);
+    assert!(is_valid_emoji_count("🇫🇷", MIN_EMOJI_COUNT, MAX_EMOJI_COUNT));
+    assert!(is_valid_emoji_count(
+        &"🔥".repeat(MAX_EMOJI_COUNT),
+        MIN_EMOJI_COUNT,
+        MAX_EMOJI_COUNT
+    ));
+    assert!(!is_valid_emoji_count(
+        &"🔥".repeat(MAX_EMOJI_COUNT + 1),
+        MIN_EMOJI_COUNT,
+        MAX_EMOJI_COUNT
+    ));
+    assert!(!is_valid_emoji_count("🔥", 2, 3));
+}
+
+#[test]
+fn test_valid_short_code() {
+    assert!(is_valid_short_code("abc_123"));
+    assert!(is_valid_short_code("🔥🍕"));
+    assert!(is_valid_short_code("🇫🇷1️⃣"));
+    assert!(!is_valid_short_code(""));
+    assert!(!is_valid_short_code("bad code"));
+    assert!(!is_valid_short_code("fire🔥"));
+}
+
+#[test]
+fn test_percent_encode_url() {
+    assert_eq!(
+        percent_encode_url("https://example.com/my file.pdf?q=a b"),
+        "https://example.com/my%20file.pdf?q=a%20b"
+    );
+    assert_eq!(
+        percent_encode_url("https://example.com/café?name=ümlaut"),
+        "https://example.com/caf%C3%A9?name=%C3%BCmlaut"
+    );
+    assert_eq!(
+        percent_encode_url("https://example.com/my%20file.pdf"),
+        "https://example.com/my%20file.pdf"
+    );
+    assert_eq!(
+        percent_encode_url("https://Example.com"),
+        "https://Example.com"
+    );
+    assert_eq!(percent_encode_url("not a url"), "not a url");
+}
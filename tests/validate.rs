@@ -0,0 +1,224 @@
+use spoo_me::{
+    errors::{PasswordViolation, ValidationError},
+    requests::{
+        EmojiRequest, ExportFormat, ExportRequest, ResolveRequest, ShortenRequest, StatsRequest,
+    },
+    utils::MAX_EMOJI_COUNT,
+};
+
+#[cfg(feature = "custom_url")]
+const BASE_URL: &str = "https://spoo.me";
+
+#[cfg(feature = "custom_url")]
+fn validate_shorten(req: &ShortenRequest) -> Result<(), ValidationError> {
+    req.validate(BASE_URL)
+}
+
+#[cfg(not(feature = "custom_url"))]
+fn validate_shorten(req: &ShortenRequest) -> Result<(), ValidationError> {
+    req.validate()
+}
+
+#[cfg(feature = "custom_url")]
+fn validate_emoji(req: &EmojiRequest) -> Result<(), ValidationError> {
+    req.validate(BASE_URL)
+}
+
+#[cfg(not(feature = "custom_url"))]
+fn validate_emoji(req: &EmojiRequest) -> Result<(), ValidationError> {
+    req.validate()
+}
+
+#[test]
+fn test_validate_shorten_request() {
+    let req = ShortenRequest::new("https://example.com")
+        .alias("my_alias")
+        .password("Valid@123")
+        .max_clicks(5);
+    assert!(validate_shorten(&req).is_ok());
+
+    assert!(matches!(
+        validate_shorten(&ShortenRequest::new("not a url")),
+        Err(ValidationError::InvalidUrlFormat(ref s)) if s == "not a url"
+    ));
+    assert!(matches!(
+        validate_shorten(&ShortenRequest::new("https://spoo.me/abc")),
+        Err(ValidationError::InvalidUrlFormat(ref s)) if s == "https://spoo.me/abc"
+    ));
+    assert!(matches!(
+        validate_shorten(&ShortenRequest::new("https://example.com").alias("bad alias")),
+        Err(ValidationError::InvalidAliasFormat(ref s)) if s == "bad alias"
+    ));
+    for alias in ["stats", "emoji", "export"] {
+        assert!(matches!(
+            validate_shorten(&ShortenRequest::new("https://example.com").alias(alias)),
+            Err(ValidationError::InvalidAliasFormat(ref s)) if s == alias
+        ));
+    }
+    assert!(matches!(
+        validate_shorten(&ShortenRequest::new("https://example.com").password("password")),
+        Err(ValidationError::InvalidPasswordFormat(
+            PasswordViolation::MissingDigit
+        ))
+    ));
+    assert!(matches!(
+        validate_shorten(&ShortenRequest::new("https://example.com").max_clicks(0)),
+        Err(ValidationError::InvalidMaxClicks(0))
+    ));
+}
+
+#[cfg(feature = "custom_url")]
+#[test]
+fn test_validate_shorten_request_custom_base_url() {
+    let req = ShortenRequest::new("https://spoo.me/abc");
+    assert!(req.validate("https://short.example.com").is_ok());
+
+    let req = ShortenRequest::new("https://short.example.com/abc");
+    assert!(matches!(
+        req.validate("https://short.example.com"),
+        Err(ValidationError::InvalidUrlFormat(ref s)) if s == "https://short.example.com/abc"
+    ));
+}
+
+#[test]
+fn test_validate_emoji_request() {
+    let req = EmojiRequest::new("https://example.com").emojies("🍕🔥");
+    assert!(validate_emoji(&req).is_ok());
+
+    assert!(matches!(
+        validate_emoji(&EmojiRequest::new("https://example.com").emojies("abc")),
+        Err(ValidationError::InvalidEmojiSequence(ref s)) if s == "abc"
+    ));
+    assert!(matches!(
+        validate_emoji(&EmojiRequest::new("https://example.com").max_clicks(0)),
+        Err(ValidationError::InvalidMaxClicks(0))
+    ));
+}
+
+#[test]
+fn test_validate_ftp_url() {
+    let shorten = ShortenRequest::new("ftp://example.com/file.txt");
+    assert!(matches!(
+        validate_shorten(&shorten),
+        Err(ValidationError::InvalidUrlFormat(ref s)) if s == "ftp://example.com/file.txt"
+    ));
+    assert!(validate_shorten(&shorten.allow_ftp(true)).is_ok());
+
+    let emoji = EmojiRequest::new("ftp://example.com/file.txt");
+    assert!(matches!(
+        validate_emoji(&emoji),
+        Err(ValidationError::InvalidUrlFormat(_))
+    ));
+    assert!(validate_emoji(&emoji.allow_ftp(true)).is_ok());
+
+    let other = ShortenRequest::new("file:///etc/passwd").allow_ftp(true);
+    assert!(matches!(
+        validate_shorten(&other),
+        Err(ValidationError::InvalidUrlFormat(_))
+    ));
+}
+
+#[test]
+fn test_validate_emoji_count() {
+    for emojies in ["🔥", "🇫🇷", "👨‍👩‍👧"] {
+        let req = EmojiRequest::new("https://example.com").emojies(emojies);
+        assert!(validate_emoji(&req).is_ok(), "{emojies} was rejected");
+    }
+
+    let too_many = "🇫🇷".repeat(MAX_EMOJI_COUNT + 1);
+    assert!(matches!(
+        validate_emoji(&EmojiRequest::new("https://example.com").emojies(too_many.as_str())),
+        Err(ValidationError::InvalidEmojiSequence(ref s)) if *s == too_many
+    ));
+
+    let req = EmojiRequest::new("https://example.com")
+        .emojies(too_many.as_str())
+        .emoji_count_limits(1, 20);
+    assert!(validate_emoji(&req).is_ok());
+    let req = EmojiRequest::new("https://example.com")
+        .emojies("🔥")
+        .emoji_count_limits(2, 4);
+    assert!(matches!(
+        validate_emoji(&req),
+        Err(ValidationError::InvalidEmojiSequence(_))
+    ));
+}
+
+#[test]
+fn test_validate_stats_request() {
+    assert!(StatsRequest::new("ga").validate().is_ok());
+    assert!(matches!(
+        StatsRequest::new("").validate(),
+        Err(ValidationError::InvalidAliasFormat(ref s)) if s.is_empty()
+    ));
+    assert!(matches!(
+        StatsRequest::new("ga").password("short").validate(),
+        Err(ValidationError::InvalidPasswordFormat(
+            PasswordViolation::TooShort
+        ))
+    ));
+}
+
+#[cfg(feature = "custom_url")]
+#[test]
+fn test_validate_delete_request() {
+    use spoo_me::requests::DeleteRequest;
+
+    assert!(DeleteRequest::new("ga").validate().is_ok());
+    assert!(DeleteRequest::new("🔥🚀").validate().is_ok());
+    assert!(matches!(
+        DeleteRequest::new("bad code").validate(),
+        Err(ValidationError::InvalidAliasFormat(ref s)) if s == "bad code"
+    ));
+    assert!(matches!(
+        DeleteRequest::new("ga").password("short").validate(),
+        Err(ValidationError::InvalidPasswordFormat(
+            PasswordViolation::TooShort
+        ))
+    ));
+}
+
+#[test]
+fn test_validate_resolve_request() {
+    assert!(ResolveRequest::new("ga").validate().is_ok());
+    assert!(ResolveRequest::new("🔥🚀").validate().is_ok());
+    assert!(matches!(
+        ResolveRequest::new("").validate(),
+        Err(ValidationError::InvalidAliasFormat(ref s)) if s.is_empty()
+    ));
+    assert!(matches!(
+        ResolveRequest::new("ga").password("short").validate(),
+        Err(ValidationError::InvalidPasswordFormat(
+            PasswordViolation::TooShort
+        ))
+    ));
+}
+
+#[test]
+fn test_validate_export_request() {
+    assert!(ExportRequest::new("ga", ExportFormat::JSON)
+        .validate()
+        .is_ok());
+    assert!(matches!(
+        ExportRequest::new("bad code", ExportFormat::CSV).validate(),
+        Err(ValidationError::InvalidAliasFormat(ref s)) if s == "bad code"
+    ));
+}
+
+#[test]
+fn test_shorten_request_try_from() {
+    let req = ShortenRequest::try_from("https://example.com").unwrap();
+    assert_eq!(req, ShortenRequest::new("https://example.com"));
+
+    let req = ShortenRequest::try_from("https://example.com/my file").unwrap();
+    assert_eq!(req.url(), "https://example.com/my%20file");
+
+    assert!(matches!(
+        ShortenRequest::try_from("not a url"),
+        Err(ValidationError::InvalidUrlFormat(ref s)) if s == "not a url"
+    ));
+    assert!(matches!(
+        ShortenRequest::try_from("https://spoo.me/abc"),
+        Err(ValidationError::InvalidUrlFormat(_))
+    ));
+}